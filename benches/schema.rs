@@ -0,0 +1,29 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rhai::Engine;
+use tlint::dsl::types::EnabledValidator;
+use tlint::dsl::validation;
+
+const CHECK: &str = include_str!("../tests/fixtures/check.yml");
+
+fn schema_only_lint(c: &mut Criterion) {
+    let json_check: serde_json::Value =
+        serde_yaml::from_str(CHECK).expect("the fixture check should be valid YAML");
+
+    c.bench_function("schema only lint", |b| {
+        b.iter(|| {
+            let json_schema = validation::get_json_schema();
+            let engine = Engine::new_raw();
+
+            validation::validate_with_rules(
+                &json_check,
+                "156F64",
+                &json_schema,
+                &engine,
+                &[EnabledValidator::Schema],
+            )
+        })
+    });
+}
+
+criterion_group!(benches, schema_only_lint);
+criterion_main!(benches);
@@ -0,0 +1,129 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use serde::{Deserialize, Serialize};
+
+use crate::dsl::types::{EnabledValidator, RuleDiagnostic, ValidationDiagnostic};
+use crate::fnv;
+
+#[derive(Deserialize)]
+struct CachedDiagnostic {
+    rule: String,
+    diagnostic: ValidationDiagnostic,
+}
+
+#[derive(Serialize)]
+struct CachingDiagnostic<'a> {
+    rule: &'a str,
+    diagnostic: &'a ValidationDiagnostic,
+}
+
+/// Returns the schema at the URL, fetching it only when it is not stored in the cache directory
+/// yet. Failing to store it only fetches it again on the next run.
+pub fn fetch_schema(
+    directory: &str,
+    url: &str,
+    fetch: impl FnOnce(&str) -> Result<String, String>,
+) -> Result<String, String> {
+    let path = Path::new(directory).join(format!("schema-{:016x}.json", fnv::hash(&[url])));
+    if let Ok(schema) = fs::read_to_string(&path) {
+        return Ok(schema);
+    }
+
+    let schema = fetch(url)?;
+    let _ = fs::create_dir_all(directory).and_then(|_| fs::write(&path, &schema));
+
+    Ok(schema)
+}
+
+/// On-disk cache of the diagnostics of the linted files, keyed by the file content and the
+/// settings the files are validated with.
+pub struct Cache {
+    directory: PathBuf,
+    settings: String,
+}
+
+impl Cache {
+    /// Opens the cache in the directory, creating it if needed. The settings identify everything
+    /// besides the file content the diagnostics depend on, e.g. the rules and the schema.
+    pub fn open(directory: &str, settings: String) -> io::Result<Cache> {
+        fs::create_dir_all(directory)?;
+
+        Ok(Cache {
+            directory: PathBuf::from(directory),
+            settings,
+        })
+    }
+
+    fn entry_path(&self, input: &str) -> PathBuf {
+        // The stable hash keeps the entries valid across toolchain upgrades.
+        let hash = fnv::hash(&[&self.settings, input]);
+
+        self.directory.join(format!("{:016x}.json", hash))
+    }
+
+    /// Returns the diagnostics stored for the input, if any.
+    pub fn get(&self, input: &str) -> Option<Vec<RuleDiagnostic>> {
+        let entry = fs::read_to_string(self.entry_path(input)).ok()?;
+        let cached: Vec<CachedDiagnostic> = serde_json::from_str(&entry).ok()?;
+
+        cached
+            .into_iter()
+            .map(|cached| {
+                let rule = EnabledValidator::from_name(&cached.rule)?.name();
+
+                Some(RuleDiagnostic {
+                    rule,
+                    diagnostic: cached.diagnostic,
+                })
+            })
+            .collect()
+    }
+
+    /// Stores the diagnostics of the input. The entry is written to a temporary file first, so
+    /// that concurrent runs never read a partially written entry. Failing to write it only
+    /// misses the cache on the next run.
+    pub fn set(&self, input: &str, diagnostics: &[RuleDiagnostic]) {
+        let cached: Vec<CachingDiagnostic> = diagnostics
+            .iter()
+            .map(|rule_diagnostic| CachingDiagnostic {
+                rule: rule_diagnostic.rule,
+                diagnostic: &rule_diagnostic.diagnostic,
+            })
+            .collect();
+        let entry = serde_json::to_string(&cached).expect("diagnostics should be serializable");
+
+        let entry_path = self.entry_path(input);
+        let temporary_path = entry_path.with_extension(format!("{}.tmp", process::id()));
+        let written = fs::write(&temporary_path, entry)
+            .and_then(|_| fs::rename(&temporary_path, &entry_path));
+        if written.is_err() {
+            let _ = fs::remove_file(&temporary_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fetch_schema_once() {
+        let directory = std::env::temp_dir().join(format!("tlint-schema-{}", process::id()));
+        let directory = directory.to_str().unwrap();
+        let url = "https://example.com/check_definition.schema.json";
+
+        let fetched = fetch_schema(directory, url, |_| Ok("{}".to_string()));
+        let cached = fetch_schema(directory, url, |_| Err("unreachable".to_string()));
+        let other = fetch_schema(directory, "https://example.com/other.json", |_| {
+            Err("unreachable".to_string())
+        });
+        fs::remove_dir_all(directory).unwrap();
+
+        assert_eq!(fetched, Ok("{}".to_string()));
+        assert_eq!(cached, Ok("{}".to_string()));
+        assert_eq!(other, Err("unreachable".to_string()));
+    }
+}
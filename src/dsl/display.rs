@@ -1,8 +1,10 @@
 use super::types::Check;
 use colored::*;
 
-fn check_header(head: &str) -> String {
-    let padding = 16 - head.len() - 4;
+pub const DEFAULT_HEADER_WIDTH: usize = 16;
+
+fn check_header(head: &str, width: usize) -> String {
+    let padding = width.saturating_sub(head.len() + 4);
     let mut header = format!("  {}  ", head);
     for _ in 0..padding {
         header.push(' ');
@@ -12,17 +14,65 @@ fn check_header(head: &str) -> String {
 }
 
 pub fn print_check(check: Check) {
-    println!("{}  {}", check_header(&check.id), check.name);
-    println!("{}  {}", check_header("Group"), check.group);
-    println!("{}  {}", check_header("Description"), check.description);
-    println!("\n{}", check_header("Remediation"));
+    print_check_with_header_width(check, DEFAULT_HEADER_WIDTH);
+}
+
+/// Prints the check, padding the headers to the given width. Headers longer than the width are
+/// printed without padding.
+pub fn print_check_with_header_width(check: Check, width: usize) {
+    println!("{}  {}", check_header(&check.id, width), check.name);
+    println!("{}  {}", check_header("Group", width), check.group);
+    println!(
+        "{}  {}",
+        check_header("Description", width),
+        check.description
+    );
+    println!("\n{}", check_header("Remediation", width));
     println!("  {}", check.remediation.replace('\n', "\n  "));
-    println!("\n{}", check_header("Facts"));
+    println!("\n{}", check_header("Facts", width));
 
     check.facts.into_iter().for_each(|fact| {
-        println!("\n  {}  {}", check_header("Name"), fact.name);
-        println!("  {}  {}", check_header("Gatherer"), fact.gatherer);
+        println!("\n  {}  {}", check_header("Name", width), fact.name);
+        println!("  {}  {}", check_header("Gatherer", width), fact.gatherer);
     });
 
-    println!("\n{}", check_header("Expectations"));
+    if let Some(values) = check.values {
+        println!("\n{}", check_header("Values", width));
+
+        values.into_iter().for_each(|value| {
+            println!("\n  {}  {}", check_header("Name", width), value.name);
+            println!("  {}  {}", check_header("Default", width), value.default);
+
+            value
+                .conditions
+                .unwrap_or_default()
+                .into_iter()
+                .for_each(|condition| {
+                    println!("    {}  {}", check_header("Value", width), condition.value);
+                    println!(
+                        "    {}  {}",
+                        check_header("When", width),
+                        condition.when.unwrap_or_default()
+                    );
+                });
+        });
+    }
+
+    println!("\n{}", check_header("Expectations", width));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pad_headers_to_width() {
+        colored::control::set_override(false);
+
+        assert_eq!(check_header("Group", 16), "  Group         ");
+        assert_eq!(
+            check_header("Very long header label", 16),
+            "  Very long header label  "
+        );
+    }
 }
@@ -0,0 +1,158 @@
+use rhai::{ASTNode, Engine, Expr, Stmt, AST};
+
+/// Keywords which are not available in the restricted engine wanda evaluates checks with.
+const DISALLOWED_SYMBOLS: [&str; 4] = ["import", "export", "eval", "fn"];
+
+/// Functions producing side effects which can't be disabled as symbols.
+const DISALLOWED_FUNCTIONS: [&str; 2] = ["print", "debug"];
+
+pub fn get_engine() -> Engine {
+    let mut engine = Engine::new();
+
+    DISALLOWED_SYMBOLS.iter().for_each(|symbol| {
+        engine.disable_symbol(*symbol);
+    });
+
+    engine
+}
+
+pub fn find_disallowed_functions(ast: &AST) -> Vec<String> {
+    let mut functions = vec![];
+
+    ast.walk(&mut |nodes: &[ASTNode]| {
+        let name = match nodes.last() {
+            Some(ASTNode::Expr(Expr::FnCall(call, _))) => Some(&call.name),
+            Some(ASTNode::Stmt(Stmt::FnCall(call, _))) => Some(&call.name),
+            Some(ASTNode::Expr(Expr::MethodCall(call, _))) => Some(&call.name),
+            _ => None,
+        };
+
+        if let Some(name) = name {
+            if DISALLOWED_FUNCTIONS.contains(&name.as_str()) {
+                functions.push(name.to_string());
+            }
+        }
+        true
+    });
+
+    functions
+}
+
+// Chained accesses like `facts.name.length` or `facts["name"].len()` nest the rest of the chain
+// into the right-hand side, with the accessed name being its leftmost expression.
+fn get_property_name(expression: &Expr) -> Option<String> {
+    match expression {
+        Expr::Property(property, _) => Some(property.2.to_string()),
+        Expr::Dot(binary, _, _) | Expr::Index(binary, _, _) => get_property_name(&binary.lhs),
+        _ => None,
+    }
+}
+
+fn get_index_name(expression: &Expr) -> Option<String> {
+    match expression {
+        Expr::StringConstant(name, _) => Some(name.to_string()),
+        Expr::Dot(binary, _, _) | Expr::Index(binary, _, _) => get_index_name(&binary.lhs),
+        _ => None,
+    }
+}
+
+fn get_reference(nodes: &[ASTNode], variable: &str) -> Option<String> {
+    match nodes.last() {
+        Some(ASTNode::Expr(Expr::Dot(binary, _, _))) => match &binary.lhs {
+            Expr::Variable(name, _, _) if name.1 == variable => get_property_name(&binary.rhs),
+            _ => None,
+        },
+        Some(ASTNode::Expr(Expr::Index(binary, _, _))) => match &binary.lhs {
+            Expr::Variable(name, _, _) if name.1 == variable => get_index_name(&binary.rhs),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Collects the names accessed on the given variable, e.g. `facts.name` or `facts["name"]`.
+pub fn collect_references(ast: &AST, variable: &str) -> Vec<String> {
+    let mut references = vec![];
+
+    ast.walk(&mut |nodes: &[ASTNode]| {
+        references.extend(get_reference(nodes, variable));
+        true
+    });
+
+    references
+}
+
+/// Collects the names accessed on the given variable by a part of an expression, e.g. the
+/// condition of a branch.
+pub fn collect_expression_references(expression: &Expr, variable: &str) -> Vec<String> {
+    let mut references = vec![];
+
+    expression.walk(&mut vec![], &mut |nodes: &[ASTNode]| {
+        references.extend(get_reference(nodes, variable));
+        true
+    });
+
+    references
+}
+
+/// Collects the names accessed on the given variable by a block of statements, e.g. the body of
+/// a branch.
+pub fn collect_statement_references(statements: &[Stmt], variable: &str) -> Vec<String> {
+    let mut references = vec![];
+
+    for statement in statements {
+        statement.walk(&mut vec![], &mut |nodes: &[ASTNode]| {
+            references.extend(get_reference(nodes, variable));
+            true
+        });
+    }
+
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reject_disallowed_symbols() {
+        let engine = get_engine();
+
+        let error = engine
+            .compile("import \"os\" as os; facts.corosync_token_timeout == 5000")
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "'import' is a reserved keyword (line 1, position 1)"
+        );
+    }
+
+    #[test]
+    fn find_disallowed_function_calls() {
+        let engine = get_engine();
+
+        let ast = engine
+            .compile("print(facts.corosync_token_timeout); facts.corosync_token_timeout == 5000")
+            .unwrap();
+
+        assert_eq!(find_disallowed_functions(&ast), vec!["print"]);
+    }
+
+    #[test]
+    fn collect_variable_references() {
+        let engine = get_engine();
+
+        let ast = engine
+            .compile(
+                "facts.timeout == values.expected && facts[\"token\"].len() > 0 && facts.nodes.count == 2",
+            )
+            .unwrap();
+
+        assert_eq!(
+            collect_references(&ast, "facts"),
+            vec!["timeout", "token", "nodes"]
+        );
+        assert_eq!(collect_references(&ast, "values"), vec!["expected"]);
+    }
+}
@@ -0,0 +1,198 @@
+use super::types::{Check, ValidationDiagnostic};
+use crate::validators::expectation_validator::ENUM_RETURN_VALUES;
+use rhai::{Dynamic, Engine, Map, Scope};
+
+/// The outcome of evaluating an expectation against sample facts and values.
+#[derive(Debug)]
+pub struct EvaluationResult {
+    pub name: String,
+    pub outcome: Result<Dynamic, ValidationDiagnostic>,
+}
+
+pub fn json_to_dynamic(value: &serde_json::Value) -> Dynamic {
+    match value {
+        serde_json::Value::Null => Dynamic::UNIT,
+        serde_json::Value::Bool(value) => Dynamic::from(*value),
+        serde_json::Value::Number(number) => match number.as_i64() {
+            Some(integer) => Dynamic::from(integer),
+            None => Dynamic::from(number.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(value) => Dynamic::from(value.clone()),
+        serde_json::Value::Array(items) => {
+            Dynamic::from_array(items.iter().map(json_to_dynamic).collect())
+        }
+        serde_json::Value::Object(properties) => Dynamic::from_map(
+            properties
+                .iter()
+                .map(|(key, value)| (key.into(), json_to_dynamic(value)))
+                .collect::<Map>(),
+        ),
+    }
+}
+
+// Values missing from the sample fall back to their default in the check.
+fn get_values(check: &Check, sample: &serde_json::Value) -> Dynamic {
+    let mut values: Map = check
+        .values
+        .iter()
+        .flatten()
+        .map(|value| (value.name.as_str().into(), json_to_dynamic(&value.default)))
+        .collect();
+
+    if let Some(sample_values) = sample.get("values").and_then(|values| values.as_object()) {
+        values.extend(
+            sample_values
+                .iter()
+                .map(|(name, value)| (name.into(), json_to_dynamic(value))),
+        );
+    }
+
+    Dynamic::from_map(values)
+}
+
+fn check_result_type(
+    result: Dynamic,
+    kind: &str,
+    check_id: &str,
+    instance_path: &str,
+) -> Result<Dynamic, ValidationDiagnostic> {
+    let is_valid = match kind {
+        "expect" => result.is_bool(),
+        "expect_enum" => {
+            result.is_string() && ENUM_RETURN_VALUES.contains(&result.to_string().as_str())
+        }
+        _ => true,
+    };
+
+    match is_valid {
+        true => Ok(result),
+        false => Err(ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: match kind {
+                "expect" => format!("expect returned {}, expected a boolean", result),
+                _ => format!(
+                    "expect_enum returned {}, expected \"passing\", \"warning\" or \"critical\"",
+                    result
+                ),
+            },
+            instance_path: instance_path.to_string(),
+        }),
+    }
+}
+
+/// Evaluates every expectation of the check with the `facts` and `values` of the sample,
+/// checking that it runs and returns a result of the expected type.
+pub fn evaluate_expectations(
+    check: &Check,
+    sample: &serde_json::Value,
+    engine: &Engine,
+) -> Vec<EvaluationResult> {
+    let facts = json_to_dynamic(sample.get("facts").unwrap_or(&serde_json::json!({})));
+    let values = get_values(check, sample);
+
+    check
+        .expectations
+        .iter()
+        .enumerate()
+        .map(|(index, expectation)| {
+            let instance_path = format!("/expectations/{:?}", index);
+            let (kind, expression) = match (
+                &expectation.expect,
+                &expectation.expect_same,
+                &expectation.expect_enum,
+            ) {
+                (Some(expression), _, _) => ("expect", expression),
+                (_, Some(expression), _) => ("expect_same", expression),
+                (_, _, Some(expression)) => ("expect_enum", expression),
+                _ => ("expect", &String::new()),
+            };
+
+            let mut scope = Scope::new();
+            scope.push_constant("facts", facts.clone());
+            scope.push_constant("values", values.clone());
+
+            let outcome = match engine.eval_with_scope::<Dynamic>(&mut scope, expression) {
+                Ok(result) => check_result_type(result, kind, &check.id, &instance_path),
+                Err(error) => Err(ValidationDiagnostic::Critical {
+                    check_id: check.id.clone(),
+                    message: error.to_string(),
+                    instance_path,
+                }),
+            };
+
+            EvaluationResult {
+                name: expectation.name.clone(),
+                outcome,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::engine::get_engine;
+
+    #[test]
+    fn evaluate_expectations_with_sample() {
+        let check: Check = serde_yaml::from_str(
+            r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: Corosync `token` timeout is set to expected value
+            remediation: Set the Corosync `token` timeout
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_token_timeout
+                default: 5000
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+              - name: timeout_status
+                expect_enum: |
+                  if facts.corosync_token_timeout == values.expected_token_timeout {
+                    "passing"
+                  } else {
+                    "failing"
+                  }
+              - name: timeout_count
+                expect: facts.corosync_token_timeout.len() > 0
+            "#,
+        )
+        .expect("the test check should be deserializable");
+        let sample = serde_json::json!({
+            "facts": { "corosync_token_timeout": 3000 }
+        });
+
+        let results = evaluate_expectations(&check, &sample, &get_engine());
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].name, "timeout");
+        assert!(matches!(&results[0].outcome, Ok(result) if !result.as_bool().unwrap()));
+
+        match &results[1].outcome {
+            Err(ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            }) => {
+                assert_eq!(
+                    message,
+                    "expect_enum returned failing, expected \"passing\", \"warning\" or \"critical\""
+                );
+                assert_eq!(instance_path, "/expectations/1");
+            }
+            outcome => panic!("Unexpected outcome {:?}", outcome),
+        }
+
+        match &results[2].outcome {
+            Err(ValidationDiagnostic::Critical { instance_path, .. }) => {
+                assert_eq!(instance_path, "/expectations/2");
+            }
+            outcome => panic!("Unexpected outcome {:?}", outcome),
+        }
+    }
+}
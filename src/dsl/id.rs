@@ -0,0 +1,42 @@
+use regex::{Captures, Regex};
+
+/// Canonical form of a check id, as wanda compares ids case sensitively.
+pub fn canonical_id(id: &str) -> String {
+    id.to_uppercase()
+}
+
+/// Rewrites the top-level `id` of a check definition to its canonical form, leaving the rest of
+/// the YAML document untouched.
+pub fn normalize_id(input: &str) -> String {
+    let id_property = Regex::new(r#"(?m)^(id:[ \t]*["']?)([^"'\s#]+)"#)
+        .expect("the id property pattern should be a valid regex");
+
+    id_property
+        .replace(input, |captures: &Captures| {
+            format!("{}{}", &captures[1], canonical_id(&captures[2]))
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_lowercase_id() {
+        let input = "id: 156f64\nname: corosync configuration file\nfacts:\n  - name: id\n    gatherer: corosync.conf\n";
+
+        assert_eq!(
+            normalize_id(input),
+            "id: 156F64\nname: corosync configuration file\nfacts:\n  - name: id\n    gatherer: corosync.conf\n"
+        );
+    }
+
+    #[test]
+    fn normalize_quoted_id() {
+        assert_eq!(
+            normalize_id("id: \"156f64\" # comment\n"),
+            "id: \"156F64\" # comment\n"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use regex::Regex;
+
+/// A link found in a check, along with the JSON pointer of the field it was found in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkRef {
+    pub url: String,
+    pub source: String,
+}
+
+const MESSAGE_FIELDS: [&str; 2] = ["failure_message", "warning_message"];
+
+// Matches bare URLs as well as the URLs of markdown links and autolinks, which are delimited by
+// the excluded characters.
+const URL_PATTERN: &str = r#"https?://[^\s<>()\[\]"'`]+"#;
+
+fn get_text_fields(json_check: &serde_json::Value) -> Vec<(String, &str)> {
+    let mut fields: Vec<(String, &str)> = ["description", "remediation"]
+        .iter()
+        .filter_map(|field| {
+            let text = json_check.get(field)?.as_str()?;
+            Some((format!("/{}", field), text))
+        })
+        .collect();
+
+    let expectations = json_check
+        .get("expectations")
+        .and_then(|expectations| expectations.as_array())
+        .into_iter()
+        .flatten()
+        .enumerate();
+
+    for (index, expectation) in expectations {
+        fields.extend(MESSAGE_FIELDS.iter().filter_map(|field| {
+            let text = expectation.get(field)?.as_str()?;
+            Some((format!("/expectations/{}/{}", index, field), text))
+        }));
+    }
+
+    fields
+}
+
+/// Extracts the links of the description, the remediation and the expectation messages of a
+/// check. No network request is made.
+pub fn extract_links(json_check: &serde_json::Value) -> Vec<LinkRef> {
+    let url_pattern = Regex::new(URL_PATTERN).expect("the URL pattern should be valid");
+    let mut links: Vec<LinkRef> = vec![];
+
+    for (source, text) in get_text_fields(json_check) {
+        for url in url_pattern.find_iter(text) {
+            let link = LinkRef {
+                url: url
+                    .as_str()
+                    .trim_end_matches(['.', ',', ';', ':', '!', '?'])
+                    .to_string(),
+                source: source.clone(),
+            };
+
+            if !links.contains(&link) {
+                links.push(link);
+            }
+        }
+    }
+
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_check_links() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value, see https://www.suse.com/docs.
+            remediation: |
+              ## Remediation
+              Follow the [documentation](https://documentation.suse.com/sle-ha/15-SP5/)
+              or <https://learn.microsoft.com/en-us/azure/sap/workloads/high-availability-guide-suse-pacemaker>.
+              See again [the docs](https://documentation.suse.com/sle-ha/15-SP5/).
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+                failure_message: Check http://example.com/token for the recommended value
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("the test string should be valid yaml");
+
+        assert_eq!(
+            extract_links(&json_value),
+            vec![
+                LinkRef {
+                    url: "https://www.suse.com/docs".to_string(),
+                    source: "/description".to_string(),
+                },
+                LinkRef {
+                    url: "https://documentation.suse.com/sle-ha/15-SP5/".to_string(),
+                    source: "/remediation".to_string(),
+                },
+                LinkRef {
+                    url: "https://learn.microsoft.com/en-us/azure/sap/workloads/high-availability-guide-suse-pacemaker".to_string(),
+                    source: "/remediation".to_string(),
+                },
+                LinkRef {
+                    url: "http://example.com/token".to_string(),
+                    source: "/expectations/0/failure_message".to_string(),
+                },
+            ]
+        );
+    }
+}
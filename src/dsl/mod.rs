@@ -1,3 +1,7 @@
 pub mod display;
+pub mod engine;
+pub mod evaluation;
+pub mod id;
+pub mod links;
 pub mod types;
 pub mod validation;
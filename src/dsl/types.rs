@@ -1,12 +1,217 @@
 use std::collections::HashMap;
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+/// A validation pass over a check definition.
+///
+/// Implement it to lint checks with additional, e.g. organization specific, rules and pass the
+/// implementations to `validation::validate_with_extra` to run them along the built-in ones.
 pub trait Validator {
+    /// Validates the check, given as the JSON representation of its YAML definition, returning
+    /// every diagnostic found. An empty list means the check is valid.
     fn validate(&self, json_check: &serde_json::Value, check_id: &str)
         -> Vec<ValidationDiagnostic>;
 }
 
+/// A validation pass over a whole catalog of checks, for rules spanning several checks.
+pub trait CatalogValidator {
+    /// Validates the checks of the catalog, returning every diagnostic found.
+    fn validate(&self, checks: &[Check]) -> Vec<CatalogDiagnostic>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnabledValidator {
+    Schema,
+    Expectation,
+    Value,
+    Metadata,
+    Fact,
+    Placeholder,
+    StrictSchema,
+    ExpectationName,
+    Deprecation,
+    Group,
+    MessageLength,
+    ValueRange,
+    IdCase,
+    TextInterpolation,
+    WarningRemediation,
+    Roundtrip,
+    QuotedNumber,
+    EnvKey,
+    HttpsLink,
+    EnumBranchValue,
+}
+
+impl EnabledValidator {
+    pub const ALL: [EnabledValidator; 20] = [
+        EnabledValidator::Schema,
+        EnabledValidator::Expectation,
+        EnabledValidator::Value,
+        EnabledValidator::Metadata,
+        EnabledValidator::Fact,
+        EnabledValidator::Placeholder,
+        EnabledValidator::StrictSchema,
+        EnabledValidator::ExpectationName,
+        EnabledValidator::Deprecation,
+        EnabledValidator::Group,
+        EnabledValidator::MessageLength,
+        EnabledValidator::ValueRange,
+        EnabledValidator::IdCase,
+        EnabledValidator::TextInterpolation,
+        EnabledValidator::WarningRemediation,
+        EnabledValidator::Roundtrip,
+        EnabledValidator::QuotedNumber,
+        EnabledValidator::EnvKey,
+        EnabledValidator::HttpsLink,
+        EnabledValidator::EnumBranchValue,
+    ];
+
+    pub const DEFAULT: [EnabledValidator; 6] = [
+        EnabledValidator::Schema,
+        EnabledValidator::Expectation,
+        EnabledValidator::Value,
+        EnabledValidator::Metadata,
+        EnabledValidator::Fact,
+        EnabledValidator::IdCase,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EnabledValidator::Schema => "schema",
+            EnabledValidator::Expectation => "expectation",
+            EnabledValidator::Value => "value",
+            EnabledValidator::Metadata => "metadata",
+            EnabledValidator::Fact => "fact",
+            EnabledValidator::Placeholder => "placeholder",
+            EnabledValidator::StrictSchema => "strict-schema",
+            EnabledValidator::ExpectationName => "expectation-name",
+            EnabledValidator::Deprecation => "deprecation",
+            EnabledValidator::Group => "group",
+            EnabledValidator::MessageLength => "message-length",
+            EnabledValidator::ValueRange => "value-range",
+            EnabledValidator::IdCase => "id-case",
+            EnabledValidator::TextInterpolation => "text-interpolation",
+            EnabledValidator::WarningRemediation => "warning-remediation",
+            EnabledValidator::Roundtrip => "roundtrip",
+            EnabledValidator::QuotedNumber => "quoted-number",
+            EnabledValidator::EnvKey => "env-key",
+            EnabledValidator::HttpsLink => "https-link",
+            EnabledValidator::EnumBranchValue => "enum-branch-value",
+        }
+    }
+
+    /// Short description of what the rule reports.
+    pub fn description(&self) -> &'static str {
+        match self {
+            EnabledValidator::Schema => "Validates the check against the check definition schema",
+            EnabledValidator::Expectation => {
+                "Validates the expectation expressions and the statuses they return"
+            }
+            EnabledValidator::Value => "Validates the values and the conditions they depend on",
+            EnabledValidator::Metadata => "Validates the metadata keys and values",
+            EnabledValidator::Fact => "Validates the gathered facts and their usage",
+            EnabledValidator::Placeholder => "Reports unrendered template placeholders",
+            EnabledValidator::StrictSchema => "Reports the properties unknown to the schema",
+            EnabledValidator::ExpectationName => {
+                "Validates the expectation names against a naming pattern"
+            }
+            EnabledValidator::Deprecation => "Reports the deprecated properties used by the check",
+            EnabledValidator::Group => "Validates the check group against the approved groups",
+            EnabledValidator::MessageLength => {
+                "Reports the expectation messages truncated by Trento"
+            }
+            EnabledValidator::ValueRange => "Reports the numeric values lower than a minimum",
+            EnabledValidator::IdCase => "Validates the check id casing",
+            EnabledValidator::TextInterpolation => {
+                "Reports interpolations in texts which are displayed as is"
+            }
+            EnabledValidator::WarningRemediation => {
+                "Reports remediations not covering the warning status"
+            }
+            EnabledValidator::Roundtrip => {
+                "Reports the properties lost when the check is loaded by Trento"
+            }
+            EnabledValidator::QuotedNumber => "Reports the numeric values written as strings",
+            EnabledValidator::EnvKey => "Reports the unknown env keys used by the conditions",
+            EnabledValidator::HttpsLink => "Reports the links not using https",
+            EnabledValidator::EnumBranchValue => {
+                "Reports the values referenced in an expect_enum branch their name doesn't hint at"
+            }
+        }
+    }
+
+    /// Whether the validator compiles the check expressions with the rhai engine.
+    pub fn uses_engine(&self) -> bool {
+        matches!(
+            self,
+            EnabledValidator::Expectation | EnabledValidator::Value
+        )
+    }
+
+    pub fn from_name(name: &str) -> Option<EnabledValidator> {
+        EnabledValidator::ALL
+            .into_iter()
+            .find(|validator| validator.name() == name)
+    }
+}
+
+pub const DEFAULT_EXPECTATION_NAME_PATTERN: &str = "^[a-z][a-z0-9_]*$";
+
+/// Length of the expectation messages over which Trento truncates them.
+pub const DEFAULT_MAX_MESSAGE_LENGTH: usize = 255;
+
+/// Lowest numeric value not reported by the value-range rule.
+pub const DEFAULT_MIN_VALUE: f64 = 0.0;
+
+/// Settings of the configurable validators.
+#[derive(Debug, Clone)]
+pub struct ValidationOptions {
+    pub expectation_name_pattern: Regex,
+    pub allowed_groups: Option<Vec<String>>,
+    /// Keys of the env the conditions may access, checked by the env-key rule.
+    pub allowed_env_keys: Option<Vec<String>>,
+    pub raw_schema_errors: bool,
+    /// Report the deprecated properties along with the schema errors.
+    pub schema_deprecations: bool,
+    pub max_message_length: usize,
+    pub min_value: f64,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            expectation_name_pattern: Regex::new(DEFAULT_EXPECTATION_NAME_PATTERN)
+                .expect("the default expectation name pattern should be a valid regex"),
+            allowed_groups: None,
+            allowed_env_keys: None,
+            raw_schema_errors: false,
+            schema_deprecations: true,
+            max_message_length: DEFAULT_MAX_MESSAGE_LENGTH,
+            min_value: DEFAULT_MIN_VALUE,
+        }
+    }
+}
+
+/// Top-level properties of a check definition.
+pub const CHECK_FIELDS: [&str; 12] = [
+    "id",
+    "name",
+    "group",
+    "metadata",
+    "when",
+    "description",
+    "remediation",
+    "severity",
+    "premium",
+    "facts",
+    "values",
+    "expectations",
+];
+
+/// Typed view of a check definition. Its required fields mirror the required properties of the
+/// check definition schema.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Check {
     pub id: String,
@@ -16,7 +221,10 @@ pub struct Check {
     pub when: Option<String>,
     pub description: String,
     pub remediation: String,
+    pub severity: Option<String>,
+    pub premium: Option<bool>,
     pub facts: Vec<FactDeclaration>,
+    pub values: Option<Vec<Value>>,
     pub expectations: Vec<Expectation>,
 }
 
@@ -27,6 +235,24 @@ pub struct FactDeclaration {
     pub argument: Option<String>,
 }
 
+// The values aren't required by the check definition schema to be parsed, so their missing
+// properties are left to the schema rule to report.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Value {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub default: serde_json::Value,
+    pub conditions: Option<Vec<ValueCondition>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ValueCondition {
+    #[serde(default)]
+    pub value: serde_json::Value,
+    pub when: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct Fact {
     pub name: String,
@@ -56,7 +282,7 @@ pub struct ParsingError {
     pub error: String,
 }
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub enum ValidationDiagnostic {
     Warning {
         check_id: String,
@@ -69,3 +295,26 @@ pub enum ValidationDiagnostic {
         instance_path: String,
     },
 }
+
+/// A diagnostic along with the name of the rule which reported it.
+#[derive(Debug)]
+pub struct RuleDiagnostic {
+    pub rule: &'static str,
+    pub diagnostic: ValidationDiagnostic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogSeverity {
+    Warning,
+    Critical,
+}
+
+/// A diagnostic about a catalog of checks. It refers to a check, or to a group when no single
+/// check is at fault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogDiagnostic {
+    pub severity: CatalogSeverity,
+    pub check_id: Option<String>,
+    pub group: String,
+    pub message: String,
+}
@@ -1,12 +1,42 @@
-use super::types::{ValidationDiagnostic, Validator};
+use super::types::{
+    CatalogDiagnostic, CatalogValidator, Check, EnabledValidator, RuleDiagnostic,
+    ValidationDiagnostic, ValidationOptions, Validator,
+};
+use crate::validators::catalog_validator::{
+    ApprovedGroupValidator, UniqueIdValidator, UniqueNameValidator,
+};
+use crate::validators::deprecation_validator::DeprecationValidator;
+use crate::validators::enum_branch_value_validator::EnumBranchValueValidator;
+use crate::validators::env_key_validator::EnvKeyValidator;
+use crate::validators::expectation_name_validator::ExpectationNameValidator;
 use crate::validators::expectation_validator::ExpectationValidator;
+use crate::validators::fact_validator::FactValidator;
+use crate::validators::group_validator::GroupValidator;
+use crate::validators::https_link_validator::HttpsLinkValidator;
+use crate::validators::id_validator::IdValidator;
+use crate::validators::message_length_validator::MessageLengthValidator;
+use crate::validators::metadata_validator::MetadataValidator;
+use crate::validators::placeholder_validator::PlaceholderValidator;
+use crate::validators::quoted_number_validator::QuotedNumberValidator;
+use crate::validators::roundtrip_validator::RoundtripValidator;
 use crate::validators::schema_validator::SchemaValidator;
+use crate::validators::strict_schema_validator::StrictSchemaValidator;
+use crate::validators::text_interpolation_validator::TextInterpolationValidator;
+use crate::validators::value_range_validator::ValueRangeValidator;
 use crate::validators::value_validator::ValueValidator;
+use crate::validators::warning_remediation_validator::WarningRemediationValidator;
 use colored::*;
 use jsonschema::{Draft, JSONSchema};
 use rhai::Engine;
 
-const SCHEMA: &str = include_str!("../../wanda/guides/check_definition.schema.json");
+/// Bundled check definition schemas, keyed by the wanda version they belong to.
+/// The last entry is the latest one and is used by default.
+pub const SCHEMAS: [(&str, &str); 1] = [(
+    "main",
+    include_str!("../../wanda/guides/check_definition.schema.json"),
+)];
+
+pub const DEFAULT_SCHEMA_VERSION: &str = SCHEMAS[SCHEMAS.len() - 1].0;
 
 pub fn error_header(head: &str) -> String {
     format!("  {}  ", head).on_red().black().to_string()
@@ -22,35 +52,347 @@ pub fn validate(
     schema: &JSONSchema,
     engine: &Engine,
 ) -> Result<(), Vec<ValidationDiagnostic>> {
-    let schema_validator = SchemaValidator { schema };
+    validate_with_rules(
+        json_check,
+        check_id,
+        schema,
+        engine,
+        &EnabledValidator::DEFAULT,
+    )
+}
+
+pub fn validate_with_rules(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    schema: &JSONSchema,
+    engine: &Engine,
+    rules: &[EnabledValidator],
+) -> Result<(), Vec<ValidationDiagnostic>> {
+    validate_with_options(
+        json_check,
+        check_id,
+        schema,
+        engine,
+        rules,
+        &ValidationOptions::default(),
+    )
+}
+
+pub fn validate_with_options(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    schema: &JSONSchema,
+    engine: &Engine,
+    rules: &[EnabledValidator],
+    options: &ValidationOptions,
+) -> Result<(), Vec<ValidationDiagnostic>> {
+    validate_with_extra(json_check, check_id, schema, engine, rules, options, &[])
+}
+
+pub fn validate_with_extra(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    schema: &JSONSchema,
+    engine: &Engine,
+    rules: &[EnabledValidator],
+    options: &ValidationOptions,
+    extra: &[&dyn Validator],
+) -> Result<(), Vec<ValidationDiagnostic>> {
+    let diagnostics: Vec<ValidationDiagnostic> =
+        validate_by_rule(json_check, check_id, schema, engine, rules, options, extra)
+            .into_iter()
+            .map(|rule_diagnostic| rule_diagnostic.diagnostic)
+            .collect();
+
+    if diagnostics.is_empty() {
+        return Ok(());
+    }
+
+    Err(diagnostics)
+}
+
+/// Validates the check like `validate_with_extra`, tagging every diagnostic with the name of the
+/// rule which reported it. Diagnostics of the extra validators are tagged as "extra".
+pub fn validate_by_rule(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    schema: &JSONSchema,
+    engine: &Engine,
+    rules: &[EnabledValidator],
+    options: &ValidationOptions,
+    extra: &[&dyn Validator],
+) -> Vec<RuleDiagnostic> {
+    let schema_validator = SchemaValidator {
+        schema,
+        raw_errors: options.raw_schema_errors,
+        deprecations: options.schema_deprecations,
+    };
     let expectation_validator = ExpectationValidator { engine };
     let value_validator = ValueValidator { engine };
+    let metadata_validator = MetadataValidator {};
+    let placeholder_validator = PlaceholderValidator {};
+    let strict_schema_validator = StrictSchemaValidator {};
+    let fact_validator = FactValidator {};
+    let group_validator = GroupValidator {
+        allowed_groups: options.allowed_groups.as_deref(),
+    };
+    let deprecation_validator = DeprecationValidator { schema };
+    let expectation_name_validator = ExpectationNameValidator {
+        pattern: &options.expectation_name_pattern,
+    };
+    let message_length_validator = MessageLengthValidator {
+        max_length: options.max_message_length,
+    };
+    let value_range_validator = ValueRangeValidator {
+        min_value: options.min_value,
+    };
+    let id_validator = IdValidator {};
+    let text_interpolation_validator = TextInterpolationValidator {};
+    let warning_remediation_validator = WarningRemediationValidator {};
+    let roundtrip_validator = RoundtripValidator {};
+    let quoted_number_validator = QuotedNumberValidator {};
+    let https_link_validator = HttpsLinkValidator {};
+    let enum_branch_value_validator = EnumBranchValueValidator {};
+    let env_key_validator = EnvKeyValidator {
+        allowed_env_keys: options.allowed_env_keys.as_deref(),
+    };
+
+    let validators: Vec<(&'static str, &dyn Validator)> = rules
+        .iter()
+        .map(|rule| -> (&'static str, &dyn Validator) {
+            let validator: &dyn Validator = match rule {
+                EnabledValidator::Schema => &schema_validator,
+                EnabledValidator::Expectation => &expectation_validator,
+                EnabledValidator::Value => &value_validator,
+                EnabledValidator::Metadata => &metadata_validator,
+                EnabledValidator::Fact => &fact_validator,
+                EnabledValidator::Placeholder => &placeholder_validator,
+                EnabledValidator::StrictSchema => &strict_schema_validator,
+                EnabledValidator::ExpectationName => &expectation_name_validator,
+                EnabledValidator::Deprecation => &deprecation_validator,
+                EnabledValidator::Group => &group_validator,
+                EnabledValidator::MessageLength => &message_length_validator,
+                EnabledValidator::ValueRange => &value_range_validator,
+                EnabledValidator::IdCase => &id_validator,
+                EnabledValidator::TextInterpolation => &text_interpolation_validator,
+                EnabledValidator::WarningRemediation => &warning_remediation_validator,
+                EnabledValidator::Roundtrip => &roundtrip_validator,
+                EnabledValidator::QuotedNumber => &quoted_number_validator,
+                EnabledValidator::EnvKey => &env_key_validator,
+                EnabledValidator::HttpsLink => &https_link_validator,
+                EnabledValidator::EnumBranchValue => &enum_branch_value_validator,
+            };
+
+            (rule.name(), validator)
+        })
+        .chain(extra.iter().map(|validator| ("extra", *validator)))
+        .collect();
 
-    let validators: Vec<&dyn Validator> =
-        vec![&schema_validator, &expectation_validator, &value_validator];
+    run_validators(json_check, check_id, &validators)
+}
 
-    let errors: Vec<ValidationDiagnostic> = validators
+fn run_validators(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    validators: &[(&'static str, &dyn Validator)],
+) -> Vec<RuleDiagnostic> {
+    validators
         .iter()
-        .flat_map(|validator| validator.validate(json_check, check_id))
-        .collect();
+        .flat_map(|(rule, validator)| {
+            let _span = tracing::debug_span!("validator", rule, check_id).entered();
+            let diagnostics = validator.validate(json_check, check_id);
+            tracing::debug!(diagnostics = diagnostics.len(), "validator finished");
+
+            diagnostics
+                .into_iter()
+                .map(|diagnostic| RuleDiagnostic { rule, diagnostic })
+        })
+        .collect()
+}
 
-    if errors.is_empty() {
-        return Ok(());
+const CHECK_RULES_POINTER: &str = "/metadata/tlint/rules";
+
+/// Refines the globally selected rules with the ones listed in the check metadata, where "rule"
+/// enables and "-rule" disables a rule. Unknown rules are reported as warnings.
+pub fn refine_rules(
+    rules: &[EnabledValidator],
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> (Vec<EnabledValidator>, Vec<ValidationDiagnostic>) {
+    let mut refined_rules = rules.to_vec();
+    let mut diagnostics = vec![];
+
+    let check_rules = json_check
+        .pointer(CHECK_RULES_POINTER)
+        .and_then(|check_rules| check_rules.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|check_rule| check_rule.as_str());
+
+    for check_rule in check_rules {
+        let (name, is_enabled) = match check_rule.strip_prefix('-') {
+            Some(name) => (name, false),
+            None => (check_rule, true),
+        };
+
+        match (EnabledValidator::from_name(name), is_enabled) {
+            (Some(rule), true) if !refined_rules.contains(&rule) => refined_rules.push(rule),
+            (Some(rule), false) => refined_rules.retain(|enabled_rule| *enabled_rule != rule),
+            (Some(_), true) => (),
+            (None, _) => diagnostics.push(ValidationDiagnostic::Warning {
+                check_id: check_id.to_string(),
+                message: format!("unknown rule '{}'", name),
+                instance_path: CHECK_RULES_POINTER.to_string(),
+            }),
+        }
+    }
+
+    (refined_rules, diagnostics)
+}
+
+/// Formats a check parse error as its message followed by the position it occurred at, as
+/// "at line L column C", or "at an unknown position" when it is not known.
+pub fn format_parse_error(error: &serde_yaml::Error) -> String {
+    let message = error.to_string();
+
+    match error.location() {
+        Some(location) => {
+            let position = format!(" at line {} column {}", location.line(), location.column());
+            let message = message
+                .split_once(&position)
+                .map_or(message.as_str(), |(message, _)| message);
+
+            format!("{}{}", message, position)
+        }
+        None => format!("{} at an unknown position", message),
     }
+}
+
+/// Validates the rules spanning the whole catalog. The approved groups rule only runs when
+/// approved groups are given.
+pub fn validate_catalog(checks: &[Check], approved_groups: &[String]) -> Vec<CatalogDiagnostic> {
+    let unique_id_validator = UniqueIdValidator {};
+    let unique_name_validator = UniqueNameValidator {};
+    let approved_group_validator = ApprovedGroupValidator {
+        groups: approved_groups,
+    };
+
+    let mut validators: Vec<&dyn CatalogValidator> =
+        vec![&unique_id_validator, &unique_name_validator];
+    if !approved_groups.is_empty() {
+        validators.push(&approved_group_validator);
+    }
+
+    validators
+        .iter()
+        .flat_map(|validator| validator.validate(checks))
+        .collect()
+}
 
-    Err(errors)
+fn get_draft(schema: &serde_json::Value) -> Option<Draft> {
+    let url = schema.get("$schema")?.as_str()?;
+
+    match url.trim_end_matches('#') {
+        "http://json-schema.org/draft-04/schema" => Some(Draft::Draft4),
+        "http://json-schema.org/draft-06/schema" => Some(Draft::Draft6),
+        "http://json-schema.org/draft-07/schema" => Some(Draft::Draft7),
+        "https://json-schema.org/draft/2019-09/schema" => Some(Draft::Draft201909),
+        "https://json-schema.org/draft/2020-12/schema" => Some(Draft::Draft202012),
+        _ => None,
+    }
 }
 
 pub fn get_json_schema() -> JSONSchema {
-    let value = serde_json::from_str(SCHEMA)
+    get_versioned_json_schema(DEFAULT_SCHEMA_VERSION)
+        .expect("the default schema version should be bundled during compilation")
+}
+
+pub fn get_versioned_json_schema(version: &str) -> Option<JSONSchema> {
+    let (_, schema) = SCHEMAS
+        .iter()
+        .find(|(schema_version, _)| *schema_version == version)?;
+
+    let value = serde_json::from_str(schema)
         .expect("a valid JSON schema should be embedded during compilation");
 
-    let compiled_schema = JSONSchema::options()
-        .with_draft(Draft::Draft201909)
-        .compile(&value)
-        .expect("a JSON schema according to draft 2019-09 aka. Draft 8 should be embedded during compilation");
+    Some(compile_schema(&value))
+}
+
+fn compile_schema(schema: &serde_json::Value) -> JSONSchema {
+    compile_json_schema(schema).expect("a valid JSON schema should be embedded during compilation")
+}
 
-    compiled_schema
+/// Compiles a check definition schema, e.g. one not bundled with tlint, with the draft declared in
+/// its `$schema`.
+pub fn compile_json_schema(schema: &serde_json::Value) -> Result<JSONSchema, String> {
+    let draft = get_draft(schema)
+        .ok_or("the schema does not declare a supported draft in its $schema".to_string())?;
+
+    JSONSchema::options()
+        .with_draft(draft)
+        .compile(&inline_references(schema, schema, &mut vec![]))
+        .map_err(|error| error.to_string())
+}
+
+// The jsonschema crate does not report annotations, like "deprecated", of sub-schemas linked
+// with "$ref". Local references are therefore replaced by the sub-schemas they point to.
+// Recursive references are kept as they are.
+fn inline_references(
+    schema: &serde_json::Value,
+    root: &serde_json::Value,
+    resolving: &mut Vec<String>,
+) -> serde_json::Value {
+    match schema {
+        serde_json::Value::Object(properties) => {
+            let reference = properties
+                .get("$ref")
+                .and_then(|reference| reference.as_str())
+                .filter(|reference| !resolving.iter().any(|resolved| resolved == reference));
+            let target = reference.and_then(|reference| {
+                root.pointer(reference.strip_prefix('#')?)
+                    .map(|target| (reference.to_string(), target))
+            });
+
+            let mut inlined: serde_json::Map<String, serde_json::Value> = properties
+                .iter()
+                .filter(|(keyword, _)| target.is_none() || *keyword != "$ref")
+                .map(|(keyword, value)| {
+                    (keyword.clone(), inline_references(value, root, resolving))
+                })
+                .collect();
+
+            let (reference, target) = match target {
+                Some(target) => target,
+                None => return serde_json::Value::Object(inlined),
+            };
+
+            resolving.push(reference);
+            let resolved = inline_references(target, root, resolving);
+            resolving.pop();
+
+            if inlined.is_empty() {
+                return resolved;
+            }
+
+            match inlined
+                .get_mut("allOf")
+                .and_then(|all_of| all_of.as_array_mut())
+            {
+                Some(all_of) => all_of.push(resolved),
+                None => {
+                    inlined.insert("allOf".to_string(), serde_json::json!([resolved]));
+                }
+            }
+            serde_json::Value::Object(inlined)
+        }
+        serde_json::Value::Array(items) => serde_json::Value::Array(
+            items
+                .iter()
+                .map(|item| inline_references(item, root, resolving))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
 }
 
 #[cfg(test)]
@@ -444,8 +786,6 @@ mod tests {
         let json_schema = get_json_schema();
         let validation_result = validate(&json_value, "156F64", &json_schema, &engine);
 
-        println!("{:?}", validation_result);
-
         assert!(validation_result.is_ok());
         assert!(deserialization_result.is_ok());
     }
@@ -490,8 +830,6 @@ mod tests {
         let json_schema = get_json_schema();
         let validation_result = validate(&json_value, "156F64", &json_schema, &engine);
 
-        println!("{:?}", validation_result);
-
         assert!(validation_result.is_ok());
         assert!(deserialization_result.is_ok());
     }
@@ -541,8 +879,6 @@ mod tests {
         let json_schema = get_json_schema();
         let validation_result = validate(&json_value, "156F64", &json_schema, &engine);
 
-        println!("{:?}", validation_result);
-
         assert!(validation_result.is_ok());
         assert!(deserialization_result.is_ok());
     }
@@ -587,8 +923,6 @@ mod tests {
         let json_schema = get_json_schema();
         let validation_result = validate(&json_value, "156F64", &json_schema, &engine);
 
-        println!("{:?}", validation_result);
-
         assert!(validation_result.is_err());
         assert!(deserialization_result.is_ok());
     }
@@ -795,7 +1129,10 @@ mod tests {
                 instance_path,
             } => {
                 assert_eq!(check_id, "156F64");
-                assert_eq!(message, "{\"failure_message\":\"critical!\",\"name\":\"timeout\"} is not valid under any of the schemas listed in the 'oneOf' keyword");
+                assert_eq!(
+                    message,
+                    "one of \"expect\", \"expect_same\" or \"expect_enum\" is a required property"
+                );
                 assert_eq!(instance_path, "/expectations/0");
             }
         }
@@ -854,9 +1191,9 @@ mod tests {
                 assert_eq!(check_id, "156F64");
                 assert_eq!(
                     message,
-                    "Open string is not terminated (line 1, position 58)"
+                    "unterminated interpolation in warning_message, close it with '}'"
                 );
-                assert_eq!(instance_path, "/expectations/0");
+                assert_eq!(instance_path, "/expectations/0/warning_message");
             }
         }
     }
@@ -963,7 +1300,7 @@ mod tests {
         let json_schema = get_json_schema();
         let validation_errors = validate(&json_value, "156F64", &json_schema, &engine).unwrap_err();
 
-        assert!(validation_errors.len() == 2);
+        assert!(validation_errors.len() == 3);
         match &validation_errors[0] {
             w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
             ValidationDiagnostic::Critical {
@@ -990,5 +1327,225 @@ mod tests {
                 assert_eq!(instance_path, "/expectations/0");
             }
         }
+        match &validation_errors[2] {
+            c @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", c),
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "expect_enum is a boolean expression returning no status. Use expect for a passing or critical result"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+    }
+
+    #[test]
+    fn get_bundled_schema_versions() {
+        assert!(get_versioned_json_schema(DEFAULT_SCHEMA_VERSION).is_some());
+        assert!(get_versioned_json_schema("unknown").is_none());
+    }
+
+    #[test]
+    fn read_draft_from_schema() {
+        let schema = serde_json::json!({
+            "$schema": "http://json-schema.org/draft-07/schema#"
+        });
+
+        assert_eq!(get_draft(&schema), Some(Draft::Draft7));
+        assert_eq!(get_draft(&serde_json::json!({})), None);
+    }
+
+    struct NamePrefixValidator {}
+
+    impl Validator for NamePrefixValidator {
+        fn validate(
+            &self,
+            json_check: &serde_json::Value,
+            check_id: &str,
+        ) -> Vec<ValidationDiagnostic> {
+            match json_check.get("name").and_then(|name| name.as_str()) {
+                Some(name) if name.starts_with("Corosync") => vec![],
+                _ => vec![ValidationDiagnostic::Critical {
+                    check_id: check_id.to_string(),
+                    message: "name should start with Corosync".to_string(),
+                    instance_path: "/name".to_string(),
+                }],
+            }
+        }
+    }
+
+    #[test]
+    fn validate_with_extra_validators() {
+        let input = r#"
+            id: 156F64
+            name: Pacemaker configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let json_schema = get_json_schema();
+
+        assert!(validate(&json_value, "156F64", &json_schema, &engine).is_ok());
+
+        let validation_errors = validate_with_extra(
+            &json_value,
+            "156F64",
+            &json_schema,
+            &engine,
+            &EnabledValidator::DEFAULT,
+            &ValidationOptions::default(),
+            &[&NamePrefixValidator {}],
+        )
+        .unwrap_err();
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(message, "name should start with Corosync");
+                assert_eq!(instance_path, "/name");
+            }
+        }
+    }
+
+    #[test]
+    fn check_struct_matches_schema_required_properties() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("the test string should be valid yaml");
+        let json_schema = get_json_schema();
+        assert!(json_schema.is_valid(&json_value));
+        assert!(serde_json::from_value::<Check>(json_value.clone()).is_ok());
+
+        let main_schema: serde_json::Value = serde_json::from_str(SCHEMAS[SCHEMAS.len() - 1].1)
+            .expect("the bundled schema should be valid json");
+        let required_properties: Vec<&str> = main_schema["required"]
+            .as_array()
+            .expect("the schema should list its required properties")
+            .iter()
+            .filter_map(|property| property.as_str())
+            .collect();
+        assert!(required_properties
+            .iter()
+            .all(|property| json_value.get(property).is_some()));
+
+        for property in json_value.as_object().unwrap().keys() {
+            let mut partial_check = json_value.clone();
+            partial_check.as_object_mut().unwrap().remove(property);
+
+            let is_required = required_properties.contains(&property.as_str());
+            assert_eq!(
+                json_schema.is_valid(&partial_check),
+                !is_required,
+                "schema and minimal check disagree on property '{}'",
+                property
+            );
+            assert_eq!(
+                serde_json::from_value::<Check>(partial_check).is_ok(),
+                !is_required,
+                "Check struct and schema disagree on whether '{}' is required",
+                property
+            );
+        }
+    }
+
+    #[test]
+    fn collect_deprecations_in_referenced_definitions() {
+        let schema = serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2019-09/schema",
+            "type": "object",
+            "properties": {
+                "facts": {
+                    "type": "array",
+                    "items": { "$ref": "#/$defs/fact" }
+                }
+            },
+            "$defs": {
+                "fact": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "legacy_gatherer": { "type": "string", "deprecated": true }
+                    }
+                }
+            }
+        });
+        let check = serde_json::json!({
+            "facts": [{ "name": "corosync_token_timeout", "legacy_gatherer": "corosync.conf" }]
+        });
+
+        let diagnostics = crate::validators::schema_validator::collect_deprecations(
+            &check,
+            "156F64",
+            &compile_schema(&schema),
+        );
+
+        assert!(diagnostics.len() == 1);
+        match &diagnostics[0] {
+            ValidationDiagnostic::Warning {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "Property 'legacy_gatherer' is deprecated and will be removed in the future"
+                );
+                assert_eq!(instance_path, "/facts/0/legacy_gatherer");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+
+    #[test]
+    fn compile_schema_without_declared_draft() {
+        let schema = serde_json::json!({ "type": "object" });
+
+        assert_eq!(
+            compile_json_schema(&schema).err(),
+            Some("the schema does not declare a supported draft in its $schema".to_string())
+        );
     }
 }
@@ -0,0 +1,25 @@
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Hashes the fields with FNV-1a, each followed by a 0 byte so that moving bytes from a field to
+/// the next one changes the hash. Unlike the standard library hasher, which may change across
+/// Rust releases, the hash stays the same between tlint builds.
+pub fn hash(fields: &[&str]) -> u64 {
+    fields.iter().fold(FNV_OFFSET_BASIS, |hash, field| {
+        field.bytes().chain([0]).fold(hash, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_fields() {
+        assert_eq!(hash(&[]), FNV_OFFSET_BASIS);
+        assert_eq!(hash(&["a"]), hash(&["a"]));
+        assert_ne!(hash(&["ab", "c"]), hash(&["a", "bc"]));
+    }
+}
@@ -1,12 +1,19 @@
 use rhai::Engine;
 
 pub mod dsl;
+mod fnv;
 
-use dsl::types::ValidationDiagnostic;
+use dsl::engine;
+use dsl::types::{Check, EnabledValidator, ValidationDiagnostic, ValidationOptions, Validator};
 use dsl::validation;
+use report::DiagnosticOutput;
 
+pub mod report;
 pub mod validators;
 
+/// Name the diagnostics of `lint_to_diagnostics` refer to the linted content with.
+pub const LINTED_CONTENT_NAME: &str = "<input>";
+
 pub fn validate(
     json_check: &serde_json::Value,
     check_id: &str,
@@ -14,10 +21,272 @@ pub fn validate(
 ) -> Result<(), Vec<ValidationDiagnostic>> {
     let json_schema = validation::get_json_schema();
 
-    validation::validate(
-        &json_check,
-        &check_id,
+    validation::validate(json_check, check_id, &json_schema, engine)
+}
+
+pub fn validate_with_extra(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    engine: &Engine,
+    extra: &[&dyn Validator],
+) -> Result<(), Vec<ValidationDiagnostic>> {
+    let json_schema = validation::get_json_schema();
+
+    validation::validate_with_extra(
+        json_check,
+        check_id,
         &json_schema,
-        &engine,
+        engine,
+        &EnabledValidator::DEFAULT,
+        &ValidationOptions::default(),
+        extra,
     )
 }
+
+/// Lints a check definition with the given rules, refined by the check metadata as the CLI does,
+/// returning its parse error or the diagnostics of every rule. Nothing is printed, so that it can
+/// be embedded in long running services.
+pub fn lint_to_diagnostics(content: &str, rules: &[EnabledValidator]) -> Vec<DiagnosticOutput> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let parse_error = |error: serde_yaml::Error| {
+        vec![DiagnosticOutput::from_parse_error(
+            LINTED_CONTENT_NAME,
+            &validation::format_parse_error(&error),
+        )]
+    };
+
+    let check: Check = match serde_yaml::from_str(content) {
+        Ok(check) => check,
+        Err(error) => return parse_error(error),
+    };
+    let json_check: serde_json::Value = match serde_yaml::from_str(content) {
+        Ok(json_check) => json_check,
+        Err(error) => return parse_error(error),
+    };
+
+    let (rules, rules_diagnostics) = validation::refine_rules(rules, &json_check, &check.id);
+    let group = Some(check.group.clone());
+    let rules_diagnostics = rules_diagnostics.iter().map(|diagnostic| DiagnosticOutput {
+        group: group.clone(),
+        ..DiagnosticOutput::from_validation(
+            LINTED_CONTENT_NAME,
+            EnabledValidator::Metadata.name(),
+            diagnostic,
+        )
+    });
+
+    let json_schema = validation::get_json_schema();
+    let diagnostics = validation::validate_by_rule(
+        &json_check,
+        &check.id,
+        &json_schema,
+        &engine::get_engine(),
+        &rules,
+        &ValidationOptions::default(),
+        &[],
+    );
+
+    rules_diagnostics
+        .chain(diagnostics.iter().map(|rule_diagnostic| DiagnosticOutput {
+            group: group.clone(),
+            ..DiagnosticOutput::from_validation(
+                LINTED_CONTENT_NAME,
+                rule_diagnostic.rule,
+                &rule_diagnostic.diagnostic,
+            )
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use report::Severity;
+
+    #[test]
+    fn lint_unparsable_content() {
+        let diagnostics = lint_to_diagnostics("name: Corosync", &EnabledValidator::DEFAULT);
+
+        assert_eq!(
+            diagnostics,
+            vec![DiagnosticOutput {
+                file: "<input>".to_string(),
+                check_id: None,
+                group: None,
+                rule: None,
+                severity: Severity::ParseError,
+                message: "missing field `id` at line 1 column 1".to_string(),
+                instance_path: String::new(),
+            }]
+        );
+    }
+
+    #[test]
+    fn lint_invalid_content() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+                warning_message: Timeout is lower than recommended
+        "#;
+
+        let diagnostics = lint_to_diagnostics(input, &EnabledValidator::DEFAULT);
+
+        assert_eq!(
+            diagnostics,
+            vec![DiagnosticOutput {
+                file: "<input>".to_string(),
+                check_id: Some("156F64".to_string()),
+                group: Some("Corosync".to_string()),
+                rule: Some("expectation".to_string()),
+                severity: Severity::Critical,
+                message: "warning_message is only available for expect_enum expectations"
+                    .to_string(),
+                instance_path: "/expectations/0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn lint_non_string_messages() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+                failure_message: null
+              - name: enum_timeout
+                expect_enum: |
+                  if facts.corosync_token_timeout == 5000 { "passing" } else { "warning" }
+                warning_message: 5
+        "#;
+
+        let diagnostics = lint_to_diagnostics(input, &[EnabledValidator::Expectation]);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                DiagnosticOutput {
+                    file: "<input>".to_string(),
+                    check_id: Some("156F64".to_string()),
+                    group: Some("Corosync".to_string()),
+                    rule: Some("expectation".to_string()),
+                    severity: Severity::Critical,
+                    message: "failure_message must be a string".to_string(),
+                    instance_path: "/expectations/0/failure_message".to_string(),
+                },
+                DiagnosticOutput {
+                    file: "<input>".to_string(),
+                    check_id: Some("156F64".to_string()),
+                    group: Some("Corosync".to_string()),
+                    rule: Some("expectation".to_string()),
+                    severity: Severity::Critical,
+                    message: "warning_message must be a string".to_string(),
+                    instance_path: "/expectations/1/warning_message".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_non_string_when() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_token_timeout
+                default: 5000
+                conditions:
+                  - value: 20000
+                    when: true
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+        "#;
+
+        let diagnostics = lint_to_diagnostics(input, &[EnabledValidator::Value]);
+
+        assert_eq!(
+            diagnostics,
+            vec![DiagnosticOutput {
+                file: "<input>".to_string(),
+                check_id: Some("156F64".to_string()),
+                group: Some("Corosync".to_string()),
+                rule: Some("value".to_string()),
+                severity: Severity::Critical,
+                message: "when must be a string".to_string(),
+                instance_path: "/values/0/conditions/0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn lint_value_without_default() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_token_timeout
+                conditions:
+                  - value: 20000
+                    when: env.provider == "azure"
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+        "#;
+
+        let diagnostics = lint_to_diagnostics(input, &[EnabledValidator::Schema]);
+
+        assert_eq!(
+            diagnostics,
+            vec![DiagnosticOutput {
+                file: "<input>".to_string(),
+                check_id: Some("156F64".to_string()),
+                group: Some("Corosync".to_string()),
+                rule: Some("schema".to_string()),
+                severity: Severity::Critical,
+                message: "\"default\" is a required property".to_string(),
+                instance_path: "/values/0".to_string(),
+            }]
+        );
+    }
+}
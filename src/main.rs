@@ -1,18 +1,40 @@
-use clap::{Parser, Subcommand};
+use clap::builder::PossibleValuesParser;
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use colored::*;
+use glob::Pattern;
+use jsonschema::JSONSchema;
+use regex::Regex;
 use rhai::Engine;
+use serde::Serialize;
+use std::cell::OnceCell;
+use std::env;
 use std::fs;
 use std::fs::File;
 use std::io;
-use std::io::Read;
+use std::io::{IsTerminal, Read, Write};
 use std::path::Path;
 use std::process;
 
+mod cache;
 pub mod dsl;
+mod fnv;
 
+use cache::Cache;
 use dsl::display;
-use dsl::types::{Check, ValidationDiagnostic};
+use dsl::engine;
+use dsl::evaluation;
+use dsl::id;
+use dsl::types::{
+    CatalogDiagnostic, CatalogSeverity, Check, EnabledValidator, FactDeclaration, RuleDiagnostic,
+    ValidationDiagnostic, ValidationOptions, DEFAULT_EXPECTATION_NAME_PATTERN,
+    DEFAULT_MAX_MESSAGE_LENGTH, DEFAULT_MIN_VALUE,
+};
 use dsl::validation;
+use report::junit::{Granularity, LintedFile};
+use report::{Counts, DiagnosticOutput, JsonStyle, Severity, Versions};
 
+pub mod report;
 pub mod validators;
 
 #[derive(Parser, Debug)]
@@ -20,34 +42,382 @@ pub mod validators;
 struct Args {
     #[clap(subcommand)]
     command: Commands,
+    /// Log what tlint does to stderr, -v for the linted files and -vv for every validation rule
+    #[clap(short = 'v', action = clap::ArgAction::Count, global = true)]
+    log_verbosity: u8,
+    /// When to color the output, "auto" coloring it when it is written to a terminal; takes
+    /// precedence over --no-color and --reproducible [default: auto]
+    #[clap(long, value_enum, value_name = "WHEN", global = true)]
+    color: Option<ColorChoice>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum ColorChoice {
+    Always,
+    Auto,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum OutputFormat {
+    Human,
+    Json,
+    Codeclimate,
+    Junit,
+    Sarif,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum GroupBy {
+    File,
+    Rule,
+    Severity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum InventoryFormat {
+    Human,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum JunitGranularity {
+    File,
+    Expectation,
+}
+
+#[derive(Debug, clap::Args)]
+struct LintArgs {
+    #[clap(short, long, value_parser, conflicts_with = "files_from")]
+    file: Option<String>,
+    /// Files or directories to lint, along with the one given with --file
+    #[clap(value_name = "TARGET", conflicts_with = "files_from")]
+    targets: Vec<String>,
+    /// Lint the check given as a YAML string instead of a file or stdin
+    #[clap(long, value_name = "YAML", conflicts_with_all = ["file", "targets", "files_from"])]
+    inline: Option<String>,
+    /// Name the check read from stdin is reported with, e.g. the path of the buffer an editor
+    /// pipes
+    #[clap(long, value_name = "NAME", conflicts_with_all = ["file", "targets", "files_from", "inline"])]
+    stdin_filename: Option<String>,
+    /// Expand $VAR and ${VAR} environment variables in the file path
+    #[clap(long)]
+    expand_env: bool,
+    /// Skip the files of the linted directory matching the glob, relative to the directory or as
+    /// a whole path
+    #[clap(long = "ignore", value_name = "GLOB", value_parser = Pattern::new)]
+    ignores: Vec<Pattern>,
+    /// Directory the paths of the linted files are reported relative to, the current directory by
+    /// default. Files out of it are reported as given
+    #[clap(long, value_name = "DIR")]
+    relative_to: Option<String>,
+    /// Lint the hidden files of the linted directory as well, whose name starts with "."
+    #[clap(long)]
+    include_hidden: bool,
+    /// Read the newline separated list of files to lint from a file, or from stdin with "-"
+    #[clap(long, value_name = "PATH")]
+    files_from: Option<String>,
+    /// Lint exactly the check files listed by a JSON manifest, as an array of paths relative to
+    /// the manifest
+    #[clap(long, value_name = "PATH", conflicts_with_all = ["file", "targets", "files_from", "inline", "stdin_filename"])]
+    manifest: Option<String>,
+    /// Disable colored output unless --color is given, same as --color never
+    #[clap(long)]
+    no_color: bool,
+    /// Deterministic output for snapshot testing: lints files sorted by path and implies
+    /// --color never unless --color is given
+    #[clap(long)]
+    reproducible: bool,
+    /// Print the status of every linted file
+    #[clap(long)]
+    verbose: bool,
+    /// Do not print the status of the linted files, even with --verbose
+    #[clap(short, long)]
+    quiet: bool,
+    /// Display a single check after it linted successfully
+    #[clap(long)]
+    show: bool,
+    /// Only print the totals of files checked, errors, warnings and parse errors
+    #[clap(long)]
+    count_only: bool,
+    /// Template of the human output lines, with the {severity}, {check_id}, {path}, {message},
+    /// {file} and {line} placeholders, replacing the default human output
+    #[clap(long, value_name = "TEMPLATE")]
+    error_format: Option<String>,
+    /// Write the results to a file instead of stdout
+    #[clap(long, value_name = "PATH")]
+    output: Option<String>,
+    /// Also write the totals of the run and the rules it ran as a JSON object to a file, next to
+    /// the report
+    #[clap(long, value_name = "PATH")]
+    summary_json: Option<String>,
+    /// Whether JUnit testcases stand for files or for expectations
+    #[clap(long, value_enum, default_value_t = JunitGranularity::File)]
+    junit_granularity: JunitGranularity,
+    /// Print the JSON based output formats on a single line
+    #[clap(long)]
+    compact: bool,
+    /// Wrap the human readable diagnostics to the given number of columns instead of the terminal
+    /// width
+    #[clap(long, value_name = "COLUMNS")]
+    wrap: Option<usize>,
+    /// Do not wrap the human readable diagnostics to the terminal width
+    #[clap(long, conflicts_with = "wrap")]
+    no_wrap: bool,
+    /// Output format of the diagnostics
+    #[clap(long, value_enum, default_value_t = OutputFormat::Human)]
+    format: OutputFormat,
+    /// Base URL of the rule documentation the SARIF rules link to, the rule name being appended
+    /// as fragment
+    #[clap(long, value_name = "URL", default_value = report::sarif::DEFAULT_DOCS_BASE_URL)]
+    docs_base_url: String,
+    /// Cluster the human readable diagnostics under a header per file, rule or severity, along
+    /// with their count
+    #[clap(long, value_enum, value_name = "KEY")]
+    group_by: Option<GroupBy>,
+    /// Version of the bundled check definition schema to validate against
+    #[clap(
+        long,
+        default_value = validation::DEFAULT_SCHEMA_VERSION,
+        value_parser = PossibleValuesParser::new(validation::SCHEMAS.map(|(version, _)| version)),
+    )]
+    schema_version: String,
+    /// URL of a check definition schema to validate against instead of a bundled one, e.g. to
+    /// preview upcoming schema changes. It is fetched once per run, or stored in the --cache
+    /// directory and fetched only once
+    #[clap(long, value_name = "URL", conflicts_with = "schema_version")]
+    schema_url: Option<String>,
+    /// Command run for every linted file, with the file path as argument, reporting additional
+    /// diagnostics. See the README for the expected output
+    #[clap(long, value_name = "COMMAND")]
+    exec: Option<String>,
+    /// Validation rules to run, "default" selects the rules enabled by default. A check can refine
+    /// them with its metadata.tlint.rules list, where "rule" enables and "-rule" disables a rule
+    #[clap(
+        long = "rule",
+        value_name = "RULE",
+        value_parser = PossibleValuesParser::new(rule_names()),
+    )]
+    rules: Vec<String>,
+    /// Fastest structural feedback, e.g. for editors: only runs the schema rule, without
+    /// building the expression engine
+    #[clap(
+        long,
+        conflicts_with_all = ["rules", "strict_schema", "groups", "env_keys", "deprecations_only"]
+    )]
+    fast: bool,
+    /// Only report the deprecated properties used by the checks
+    #[clap(long)]
+    deprecations_only: bool,
+    /// Fail when warnings are found, including the deprecated properties reported in
+    /// --deprecations-only mode and the parse errors allowed with --allow-parse-errors
+    #[clap(long)]
+    strict: bool,
+    /// Report the files which can't be parsed as warnings not failing the run, e.g. for partially
+    /// migrated catalogs
+    #[clap(long)]
+    allow_parse_errors: bool,
+    /// Report the structured output of the schema validation instead of summarized messages
+    #[clap(long)]
+    raw_schema_errors: bool,
+    /// Do not report the deprecated properties used by the checks with the schema rule, which
+    /// still validates their structure
+    #[clap(
+        long,
+        alias = "no-schema-deprecations",
+        conflicts_with = "deprecations_only"
+    )]
+    no_deprecation_warnings: bool,
+    /// File with the newline separated groups checks may belong to, enables the group rule
+    #[clap(long, value_name = "PATH")]
+    groups: Option<String>,
+    /// File with the newline separated env keys the conditions may access, enables the env-key
+    /// rule
+    #[clap(long, value_name = "PATH")]
+    env_keys: Option<String>,
+    /// Exit code to use for warnings, 0 by default so that warnings don't fail the run unless
+    /// opted in
+    #[clap(long, alias = "warning-exit", value_name = "CODE")]
+    warn_exit_code: Option<i32>,
+    /// Exit code to use for errors
+    #[clap(long, value_name = "CODE", default_value_t = 1)]
+    error_exit: i32,
+    /// Exit code to use for files which can't be parsed
+    #[clap(long, value_name = "CODE", default_value_t = 1)]
+    parse_error_exit: i32,
+    /// Warn about top-level properties which are not known check properties
+    #[clap(long)]
+    strict_schema: bool,
+    /// Naming convention expectation names are checked against by the expectation-name rule
+    #[clap(long, value_name = "REGEX", default_value = DEFAULT_EXPECTATION_NAME_PATTERN, value_parser = Regex::new)]
+    expectation_name_pattern: Regex,
+    /// Length over which expectation messages are reported by the message-length rule
+    #[clap(long, value_name = "LENGTH", default_value_t = DEFAULT_MAX_MESSAGE_LENGTH)]
+    max_message_length: usize,
+    /// Lowest numeric value default or condition value not reported by the value-range rule
+    #[clap(long, value_name = "VALUE", default_value_t = DEFAULT_MIN_VALUE, allow_negative_numbers = true)]
+    min_value: f64,
+    /// Directory caching the diagnostics of the linted files, so that unchanged files are not
+    /// validated again. Ignored together with --schema-url, as the fetched schema may change
+    #[clap(long, value_name = "DIR")]
+    cache: Option<String>,
+    /// Print the configuration the lint would run with, once the flags are combined with the
+    /// defaults, and exit. Printed as JSON with --format json
+    #[clap(long)]
+    print_config: bool,
 }
 
 #[derive(Debug, Subcommand)]
 enum Commands {
-    Lint {
-        #[clap(short, long, value_parser)]
-        file: Option<String>,
-    },
+    Lint(Box<LintArgs>),
     Show {
         #[clap(short, long, value_parser)]
         file: Option<String>,
     },
+    /// Evaluate the expectations of a check against sample facts and values
+    Eval {
+        check: String,
+        /// JSON file with the sample "facts" and "values" objects
+        #[clap(long, value_name = "PATH")]
+        facts: String,
+    },
+    /// Validate the rules spanning all the checks of a catalog directory
+    Catalog {
+        directory: String,
+        /// Comma separated list of the groups checks may belong to
+        #[clap(long, value_name = "GROUPS", value_delimiter = ',')]
+        approved_groups: Vec<String>,
+    },
+    /// List the facts of the checks of a catalog directory and the gatherers they use, without
+    /// validating the checks
+    Inventory {
+        directory: String,
+        /// Output format of the inventory
+        #[clap(long, value_enum, default_value_t = InventoryFormat::Human)]
+        format: InventoryFormat,
+    },
+    /// Rewrite the id of a check to its canonical uppercase form
+    NormalizeId {
+        file: String,
+        /// Write the normalized check back to the file instead of printing it
+        #[clap(long)]
+        write: bool,
+    },
+    /// Print the completion script of a shell
+    Completions {
+        #[clap(value_enum)]
+        shell: Shell,
+    },
 }
 
-fn get_input(file: Option<String>) -> String {
-    let mut payload = String::new();
+const DEFAULT_RULES: &str = "default";
+
+fn rule_names() -> Vec<&'static str> {
+    let mut names = vec![DEFAULT_RULES];
+    names.extend(EnabledValidator::ALL.iter().map(EnabledValidator::name));
+    names
+}
+
+fn normalize_rules(rules: &[String]) -> Vec<EnabledValidator> {
+    if rules.is_empty() {
+        return EnabledValidator::DEFAULT.to_vec();
+    }
+
+    EnabledValidator::ALL
+        .into_iter()
+        .filter(|validator| {
+            rules.iter().any(|rule| {
+                rule == validator.name()
+                    || (rule == DEFAULT_RULES && EnabledValidator::DEFAULT.contains(validator))
+            })
+        })
+        .collect()
+}
+
+const UTF8_BOM: &str = "\u{feff}";
+
+fn get_input(file: Option<String>) -> Result<String, String> {
+    read_input(file).and_then(decode_input)
+}
+
+fn read_input(file: Option<String>) -> Result<Vec<u8>, String> {
+    let mut payload = Vec::new();
     match file {
-        Some(file_path) => {
-            let mut file = File::open(file_path).expect("Unable to open file");
-            file.read_to_string(&mut payload).expect("");
-        }
-        None => {
-            io::stdin()
-                .read_to_string(&mut payload)
-                .expect("Unable to read from stdin");
-        }
+        Some(file_path) => File::open(&file_path)
+            .and_then(|mut file| file.read_to_end(&mut payload))
+            .map_err(|error| format!("unable to read {}: {}", file_path, error))?,
+        None => io::stdin()
+            .read_to_end(&mut payload)
+            .map_err(|error| format!("unable to read from stdin: {}", error))?,
+    };
+    Ok(payload)
+}
+
+fn decode_input(payload: Vec<u8>) -> Result<String, String> {
+    let payload = String::from_utf8(payload)
+        .map_err(|error| format!("input is not valid UTF-8: {}", error))?;
+
+    match payload.strip_prefix(UTF8_BOM) {
+        Some(stripped) => Ok(stripped.to_string()),
+        None => Ok(payload),
+    }
+}
+
+fn get_input_or_exit(file: Option<String>) -> String {
+    get_input(file).unwrap_or_else(|error| {
+        eprintln!("{} - {}", validation::error_header("Invalid input"), error);
+        process::exit(1);
+    })
+}
+
+fn expand_env(path: &str) -> Result<String, String> {
+    let variable_pattern =
+        Regex::new(r"\$(?:\{(\w+)\}|(\w+))").expect("the variable pattern should be valid");
+
+    let mut expanded = String::new();
+    let mut last_match_end = 0;
+    for captures in variable_pattern.captures_iter(path) {
+        let variable = captures.get(0).expect("a capture should contain its match");
+        let name = captures
+            .get(1)
+            .or_else(|| captures.get(2))
+            .expect("a variable reference should contain a name")
+            .as_str();
+        let value =
+            env::var(name).map_err(|_| format!("environment variable '{}' is not set", name))?;
+
+        expanded.push_str(&path[last_match_end..variable.start()]);
+        expanded.push_str(&value);
+        last_match_end = variable.end();
     }
-    payload
+    expanded.push_str(&path[last_match_end..]);
+
+    Ok(expanded)
+}
+
+fn download_schema(url: &str) -> Result<String, String> {
+    let response = ureq::get(url)
+        .call()
+        .map_err(|error| format!("unable to fetch the schema: {}", error))?;
+
+    response
+        .into_string()
+        .map_err(|error| format!("unable to fetch the schema: {}", error))
+}
+
+// Fetches the schema at the URL, through the cache directory if any so that it is downloaded
+// once, and returns it along with its compiled form.
+fn fetch_schema(url: &str, cache_directory: Option<&str>) -> Result<(String, JSONSchema), String> {
+    let raw_schema = match cache_directory {
+        Some(directory) => cache::fetch_schema(directory, url, download_schema)?,
+        None => download_schema(url)?,
+    };
+    let schema: serde_json::Value = serde_json::from_str(&raw_schema)
+        .map_err(|error| format!("the schema at {} is not valid JSON: {}", url, error))?;
+    let compiled_schema = validation::compile_json_schema(&schema)
+        .map_err(|error| format!("unable to compile the schema at {}: {}", url, error))?;
+
+    Ok((raw_schema, compiled_schema))
 }
 
 fn is_directory(arg_path: Option<String>) -> bool {
@@ -57,7 +427,9 @@ fn is_directory(arg_path: Option<String>) -> bool {
     }
 }
 
-fn scan_directory(directory: &str) -> Result<Vec<String>, std::io::Error> {
+// Lists the files of the directory, skipping the hidden ones, such as editor backups, unless
+// they are included.
+fn scan_directory(directory: &str, include_hidden: bool) -> Result<Vec<String>, std::io::Error> {
     let files_list = fs::read_dir(directory)?
         .filter_map(|file| {
             file.ok().and_then(|e| match e.path().is_file() {
@@ -65,152 +437,1348 @@ fn scan_directory(directory: &str) -> Result<Vec<String>, std::io::Error> {
                 false => None,
             })
         })
+        .filter(|path| include_hidden || !is_hidden_file(path))
         .collect();
     Ok(files_list)
 }
 
-fn main() -> Result<(), serde_yaml::Error> {
-    let args = Args::parse();
-    let engine = Engine::new();
+fn is_hidden_file(path: &str) -> bool {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'))
+}
 
-    match args.command {
-        Commands::Lint { file } => match is_directory(file.clone()) {
-            true => {
-                if let Some(directory) = file {
-                    let json_schema = validation::get_json_schema();
-                    let files = scan_directory(&directory).expect("Unable to scan directory");
-                    let mut parsing_errors = vec![];
-                    let (_, validation_errors): (Vec<_>, Vec<_>) = files
-                        .into_iter()
-                        .filter(|check_path| {
-                            let extension = Path::new(check_path).extension();
-                            match extension {
-                                Some(s) => s == "yml" || s == "yaml",
-                                None => false,
-                            }
-                        })
-                        .map(|check_path| {
-                            let input = get_input(Some(check_path));
-                            let json_value: serde_json::Value = serde_yaml::from_str(&input)
-                                .expect("Unable to parse the YAML into a JSON payload");
-                            let deserialization_result = serde_yaml::from_str::<Check>(&input);
-
-                            match deserialization_result {
-                                Err(ref error) => {
-                                    parsing_errors.push(error.to_string());
-                                    Ok(())
-                                }
-                                Ok(check) => {
-                                    let check_id = check.id;
-
-                                    validation::validate(
-                                        &json_value,
-                                        &check_id,
-                                        &json_schema,
-                                        &engine,
-                                    )
-                                }
-                            }
-                        })
-                        .partition(Result::is_ok);
+fn is_ignored(directory: &str, path: &str, ignores: &[Pattern]) -> bool {
+    let relative_path = Path::new(path)
+        .strip_prefix(directory)
+        .unwrap_or(Path::new(path));
 
-                    let exit_code = match parsing_errors.is_empty() && validation_errors.is_empty()
-                    {
-                        true => 0,
-                        false => 1,
-                    };
+    ignores
+        .iter()
+        .any(|ignore| ignore.matches_path(relative_path) || ignore.matches(path))
+}
 
-                    for error in parsing_errors {
-                        println!("{} - {}", validation::error_header("Parse error"), error);
-                    }
+// Makes the path relative to the base directory when it is in it, both being taken from the
+// current directory when relative.
+fn relative_path(path: &str, base: &Path) -> String {
+    let current_directory = env::current_dir().unwrap_or_default();
+    let absolute_path = current_directory.join(path);
+    let absolute_base = current_directory.join(base);
 
-                    validation_errors
-                        .into_iter()
-                        .flat_map(Result::unwrap_err)
-                        .for_each(|diagnostic| match diagnostic {
-                            ValidationDiagnostic::Warning {
-                                check_id,
-                                message,
-                                instance_path,
-                            } => {
-                                println!("{} - {}", validation::warning_header(&check_id), message);
-                                println!("  path: {}\n", instance_path);
-                            }
-                            ValidationDiagnostic::Critical {
-                                check_id,
-                                message,
-                                instance_path,
-                            } => {
-                                println!("{} - {}", validation::error_header(&check_id), message);
-                                println!("  path: {}\n", instance_path);
-                            }
-                        });
+    match absolute_path.strip_prefix(&absolute_base) {
+        Ok(relative) => relative.to_string_lossy().to_string(),
+        Err(_) => path.to_string(),
+    }
+}
+
+// Resolves a lint target to the files to lint: the target itself, or the YAML files of the
+// directory not ignored. A directory which can't be scanned is kept as target, so that it is
+// reported as not readable along with the results of the other targets.
+fn resolve_target(target: &str, ignores: &[Pattern], include_hidden: bool) -> Vec<String> {
+    if !Path::new(target).is_dir() {
+        return vec![target.to_string()];
+    }
+
+    match scan_directory(target, include_hidden) {
+        Ok(files) => files
+            .into_iter()
+            .filter(|check_path| is_yaml_file(check_path))
+            .filter(|check_path| !is_ignored(target, check_path, ignores))
+            .collect(),
+        Err(_) => vec![target.to_string()],
+    }
+}
+
+fn read_list(list_path: &str) -> Vec<String> {
+    let source = match list_path {
+        "-" => None,
+        path => Some(path.to_string()),
+    };
+
+    get_input_or_exit(source)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// Reads the check files listed by a manifest, resolved relative to the manifest directory.
+fn read_manifest(manifest_path: &str) -> Result<Vec<String>, String> {
+    let content = fs::read_to_string(manifest_path)
+        .map_err(|error| format!("unable to read {}: {}", manifest_path, error))?;
+    let files: Vec<String> = serde_json::from_str(&content)
+        .map_err(|error| format!("{} is not an array of paths: {}", manifest_path, error))?;
+    let directory = Path::new(manifest_path).parent().unwrap_or(Path::new(""));
+
+    Ok(files
+        .iter()
+        .map(|file| directory.join(file).to_string_lossy().into_owned())
+        .collect())
+}
+
+fn is_yaml_file(path: &str) -> bool {
+    match Path::new(path).extension() {
+        Some(extension) => extension == "yml" || extension == "yaml",
+        None => false,
+    }
+}
+
+/// Why a file couldn't be validated.
+enum LintError {
+    /// The file can't be read or isn't a check.
+    Parse(String),
+    /// The file doesn't exist or can't be read, e.g. when listed by the manifest, which is never
+    /// allowed.
+    MissingFile(String),
+}
+
+impl LintError {
+    fn message(&self) -> &str {
+        match self {
+            LintError::Parse(error) | LintError::MissingFile(error) => error,
+        }
+    }
+
+    fn header(&self) -> &'static str {
+        match self {
+            LintError::Parse(_) => "Parse error",
+            LintError::MissingFile(_) => "Missing file",
+        }
+    }
+}
+
+struct LintResult {
+    file: String,
+    check: Option<Check>,
+    outcome: Result<Vec<RuleDiagnostic>, LintError>,
+    /// Whether a parse error is reported as a warning, see --allow-parse-errors.
+    parse_error_allowed: bool,
+}
+
+impl LintResult {
+    fn is_ok(&self) -> bool {
+        matches!(&self.outcome, Ok(diagnostics) if diagnostics.is_empty())
+    }
 
-                    process::exit(exit_code);
+    fn is_allowed_parse_error(&self) -> bool {
+        self.parse_error_allowed && matches!(self.outcome, Err(LintError::Parse(_)))
+    }
+
+    fn diagnostics(&self) -> Vec<DiagnosticOutput> {
+        match &self.outcome {
+            Ok(diagnostics) => diagnostics
+                .iter()
+                .map(|rule_diagnostic| DiagnosticOutput {
+                    group: self.check.as_ref().map(|check| check.group.clone()),
+                    ..DiagnosticOutput::from_validation(
+                        &self.file,
+                        rule_diagnostic.rule,
+                        &rule_diagnostic.diagnostic,
+                    )
+                })
+                .collect(),
+            Err(error @ LintError::MissingFile(_)) => vec![DiagnosticOutput::from_missing_file(
+                &self.file,
+                error.message(),
+            )],
+            Err(error) => {
+                let mut diagnostic =
+                    DiagnosticOutput::from_parse_error(&self.file, error.message());
+                if self.is_allowed_parse_error() {
+                    diagnostic.severity = Severity::Warning;
                 }
+
+                vec![diagnostic]
             }
-            false => {
-                let input = get_input(file);
-                let json_value: serde_json::Value = serde_yaml::from_str(&input)?;
-                let deserialization_result = serde_yaml::from_str::<Check>(&input);
+        }
+    }
+}
+
+fn lint_file(
+    file: Option<String>,
+    json_schema: &JSONSchema,
+    engine: &OnceCell<Engine>,
+    rules: &[EnabledValidator],
+    options: &ValidationOptions,
+    cache: Option<&Cache>,
+    exec: Option<&str>,
+) -> LintResult {
+    let file_name = file.clone().unwrap_or_else(|| "<stdin>".to_string());
+
+    let _span = tracing::info_span!("lint", file = file_name).entered();
+    tracing::info!("linting started");
+    let input = read_input(file.clone())
+        .map_err(LintError::MissingFile)
+        .and_then(|payload| decode_input(payload).map_err(LintError::Parse));
+    let mut result = lint_input(file_name, input, json_schema, engine, rules, options, cache);
+    if let (Some(command), Some(file)) = (exec, &file) {
+        if let (Ok(diagnostics), Some(check)) = (&mut result.outcome, &result.check) {
+            diagnostics.extend(
+                run_exec(command, file, &check.id)
+                    .into_iter()
+                    .map(|diagnostic| RuleDiagnostic {
+                        rule: EXEC_RULE,
+                        diagnostic,
+                    }),
+            );
+        }
+    }
+    tracing::info!(ok = result.is_ok(), "linting finished");
 
-                if let Err(ref error) = deserialization_result {
-                    println!("{} - {}", validation::error_header("Parse error"), error);
-                    process::exit(1)
+    result
+}
+
+const EXEC_RULE: &str = "exec";
+
+// Runs the --exec command with the check file path as argument. Every line it prints is a
+// diagnostic, as "warning|critical <path> <message>", and a non-zero exit status is an error.
+fn run_exec(command: &str, file: &str, check_id: &str) -> Vec<ValidationDiagnostic> {
+    let output = process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", command))
+        .arg("sh")
+        .arg(file)
+        .output();
+    let output = match output {
+        Ok(output) => output,
+        Err(error) => {
+            return vec![ValidationDiagnostic::Critical {
+                check_id: check_id.to_string(),
+                message: format!("unable to run '{}': {}", command, error),
+                instance_path: String::new(),
+            }]
+        }
+    };
+
+    let mut diagnostics: Vec<ValidationDiagnostic> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_exec_diagnostic(command, line, check_id))
+        .collect();
+
+    if !output.status.success() {
+        diagnostics.push(ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: format!("'{}' failed with {}", command, output.status),
+            instance_path: String::new(),
+        });
+    }
+
+    diagnostics
+}
+
+fn parse_exec_diagnostic(command: &str, line: &str, check_id: &str) -> ValidationDiagnostic {
+    let mut fields = line.trim().splitn(3, char::is_whitespace);
+
+    match (fields.next(), fields.next(), fields.next()) {
+        (Some("warning"), Some(path), Some(message)) => ValidationDiagnostic::Warning {
+            check_id: check_id.to_string(),
+            message: message.trim().to_string(),
+            instance_path: path.to_string(),
+        },
+        (Some("critical"), Some(path), Some(message)) => ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: message.trim().to_string(),
+            instance_path: path.to_string(),
+        },
+        _ => ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: format!("unexpected output of '{}': {}", command, line),
+            instance_path: String::new(),
+        },
+    }
+}
+
+fn lint_input(
+    file_name: String,
+    input: Result<String, LintError>,
+    json_schema: &JSONSchema,
+    engine: &OnceCell<Engine>,
+    rules: &[EnabledValidator],
+    options: &ValidationOptions,
+    cache: Option<&Cache>,
+) -> LintResult {
+    let input = match input {
+        Ok(input) => input,
+        Err(error) => {
+            return LintResult {
+                file: file_name,
+                check: None,
+                outcome: Err(error),
+                parse_error_allowed: false,
+            }
+        }
+    };
+
+    let (check, outcome) = match serde_yaml::from_str::<Check>(&input) {
+        Err(error) => (
+            None,
+            Err(LintError::Parse(validation::format_parse_error(&error))),
+        ),
+        Ok(check) => match cache.and_then(|cache| cache.get(&input)) {
+            Some(diagnostics) => (Some(check), Ok(diagnostics)),
+            None => {
+                let diagnostics =
+                    validate_input(&input, &check, json_schema, engine, rules, options);
+                if let Some(cache) = cache {
+                    cache.set(&input, &diagnostics);
                 }
 
-                let check = deserialization_result.unwrap();
-                let check_id = check.id;
-                let json_schema = validation::get_json_schema();
-                let validation_result =
-                    validation::validate(&json_value, &check_id, &json_schema, &engine);
-
-                let exit_code = match validation_result {
-                    Ok(_) => 0,
-                    Err(validation_errors) => {
-                        validation_errors
-                            .iter()
-                            .for_each(|diagnostic| match diagnostic {
-                                ValidationDiagnostic::Warning {
-                                    check_id,
-                                    message,
-                                    instance_path,
-                                } => {
-                                    println!(
-                                        "{} - {}",
-                                        validation::warning_header(&check_id),
-                                        message
-                                    );
-                                    println!("  path: {}\n", instance_path);
-                                }
-                                ValidationDiagnostic::Critical {
-                                    check_id,
-                                    message,
-                                    instance_path,
-                                } => {
-                                    println!(
-                                        "{} - {}",
-                                        validation::error_header(&check_id),
-                                        message
-                                    );
-                                    println!("  path: {}\n", instance_path);
-                                }
-                            });
-                        1
-                    }
+                (Some(check), Ok(diagnostics))
+            }
+        },
+    };
+
+    LintResult {
+        file: file_name,
+        check,
+        outcome,
+        parse_error_allowed: false,
+    }
+}
+
+/// Identifies everything besides the file content the diagnostics depend on, so that changing
+/// the rules, their options, tlint or the schema invalidates the cached diagnostics.
+fn cache_settings(schema: &str, rules: &[EnabledValidator], options: &ValidationOptions) -> String {
+    let rules: Vec<&str> = rules.iter().map(EnabledValidator::name).collect();
+
+    format!(
+        "{}\n{}\n{:?}\n{}",
+        env!("CARGO_PKG_VERSION"),
+        rules.join(","),
+        options,
+        schema
+    )
+}
+
+fn validate_input(
+    input: &str,
+    check: &Check,
+    json_schema: &JSONSchema,
+    engine: &OnceCell<Engine>,
+    rules: &[EnabledValidator],
+    options: &ValidationOptions,
+) -> Vec<RuleDiagnostic> {
+    let json_value: serde_json::Value =
+        serde_yaml::from_str(input).expect("a deserializable check should be a valid JSON payload");
+
+    let (rules, rules_diagnostics) = validation::refine_rules(rules, &json_value, &check.id);
+    let mut diagnostics: Vec<RuleDiagnostic> = rules_diagnostics
+        .into_iter()
+        .map(|diagnostic| RuleDiagnostic {
+            rule: EnabledValidator::Metadata.name(),
+            diagnostic,
+        })
+        .collect();
+    // The engine is only built when a rule compiles expressions, a bare one being enough
+    // for the other rules.
+    let raw_engine;
+    let engine = match rules.iter().any(EnabledValidator::uses_engine) {
+        true => engine.get_or_init(engine::get_engine),
+        false => {
+            raw_engine = Engine::new_raw();
+            &raw_engine
+        }
+    };
+    diagnostics.extend(validation::validate_by_rule(
+        &json_value,
+        &check.id,
+        json_schema,
+        engine,
+        &rules,
+        options,
+        &[],
+    ));
+
+    diagnostics
+}
+
+fn print_file_status(out: &mut dyn Write, result: &LintResult) -> io::Result<()> {
+    match &result.outcome {
+        Ok(diagnostics) if diagnostics.is_empty() => {
+            writeln!(out, "{} {}", "✓".green(), result.file)
+        }
+        Ok(diagnostics) => {
+            let errors = diagnostics
+                .iter()
+                .filter(|rule_diagnostic| {
+                    matches!(
+                        rule_diagnostic.diagnostic,
+                        ValidationDiagnostic::Critical { .. }
+                    )
+                })
+                .count();
+            let warnings = diagnostics.len() - errors;
+
+            let mut counts = vec![];
+            if errors > 0 {
+                counts.push(pluralize(errors, "error"));
+            }
+            if warnings > 0 {
+                counts.push(pluralize(warnings, "warning"));
+            }
+
+            writeln!(out, "{} {} ({})", "✗".red(), result.file, counts.join(", "))
+        }
+        Err(error) => {
+            writeln!(
+                out,
+                "{} {} ({})",
+                "✗".red(),
+                result.file,
+                error.header().to_lowercase()
+            )
+        }
+    }
+}
+
+fn pluralize(count: usize, noun: &str) -> String {
+    match count {
+        1 => format!("{} {}", count, noun),
+        _ => format!("{} {}s", count, noun),
+    }
+}
+
+// Width the human output is wrapped to: the one given with --wrap, or the terminal width when
+// printing to a terminal.
+fn get_wrap_width(args: &LintArgs) -> Option<usize> {
+    if args.no_wrap {
+        return None;
+    }
+
+    args.wrap.or_else(|| match io::stdout().is_terminal() {
+        true => terminal_size::terminal_size().map(|(width, _)| width.0 as usize),
+        false => None,
+    })
+}
+
+// Wraps the message following a header, indenting the continuation lines under the message.
+fn wrap_message(message: &str, header: &str, wrap_width: Option<usize>) -> String {
+    let indent = header.len() + 4 + " - ".len();
+
+    match wrap_width {
+        Some(width) if width > indent => {
+            textwrap::wrap(message, width - indent).join(&format!("\n{}", " ".repeat(indent)))
+        }
+        _ => message.to_string(),
+    }
+}
+
+// Prints the diagnostic, along with the file it belongs to and the rule which reported it if
+// given.
+fn print_diagnostic(
+    out: &mut dyn Write,
+    diagnostic: &ValidationDiagnostic,
+    file: Option<&str>,
+    rule: Option<&str>,
+    wrap_width: Option<usize>,
+) -> io::Result<()> {
+    let (header, check_id, message, instance_path) = match diagnostic {
+        ValidationDiagnostic::Warning {
+            check_id,
+            message,
+            instance_path,
+        } => (
+            validation::warning_header(check_id),
+            check_id,
+            message,
+            instance_path,
+        ),
+        ValidationDiagnostic::Critical {
+            check_id,
+            message,
+            instance_path,
+        } => (
+            validation::error_header(check_id),
+            check_id,
+            message,
+            instance_path,
+        ),
+    };
+
+    writeln!(
+        out,
+        "{} - {}",
+        header,
+        wrap_message(message, check_id, wrap_width)
+    )?;
+    writeln!(out, "  path: {}", instance_path)?;
+    if let Some(file) = file {
+        writeln!(out, "  file: {}", file)?;
+    }
+    if let Some(rule) = rule {
+        writeln!(out, "  rule: {}", rule)?;
+    }
+    writeln!(out)
+}
+
+fn collect_diagnostics(results: &[LintResult]) -> Vec<DiagnosticOutput> {
+    results.iter().flat_map(LintResult::diagnostics).collect()
+}
+
+// Prints the diagnostics of every file, preceded by its status when show_status is set, and with
+// the file they belong to when show_files is set, e.g. when only the deprecations are reported.
+fn print_results(
+    out: &mut dyn Write,
+    results: &[LintResult],
+    show_status: bool,
+    verbose: bool,
+    show_files: bool,
+    error_format: Option<&str>,
+    wrap_width: Option<usize>,
+) -> io::Result<()> {
+    for result in results {
+        if show_status {
+            print_file_status(out, result)?;
+        }
+
+        if let Some(template) = error_format {
+            for diagnostic in result.diagnostics() {
+                writeln!(out, "{}", report::template::format(template, &diagnostic))?;
+            }
+            continue;
+        }
+
+        match &result.outcome {
+            Err(error) => {
+                let header = match result.is_allowed_parse_error() {
+                    true => validation::warning_header(error.header()),
+                    false => validation::error_header(error.header()),
                 };
+                writeln!(
+                    out,
+                    "{} - {}",
+                    header,
+                    wrap_message(error.message(), error.header(), wrap_width)
+                )?;
+            }
+            Ok(diagnostics) => {
+                for rule_diagnostic in diagnostics {
+                    let file = Some(result.file.as_str()).filter(|_| show_files);
+                    let rule = Some(rule_diagnostic.rule).filter(|_| verbose);
+                    print_diagnostic(out, &rule_diagnostic.diagnostic, file, rule, wrap_width)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn get_group(diagnostic: &DiagnosticOutput, group_by: GroupBy) -> &str {
+    match (group_by, &diagnostic.rule, diagnostic.severity) {
+        (GroupBy::File, _, _) => &diagnostic.file,
+        (GroupBy::Rule, Some(rule), _) => rule,
+        (_, _, Severity::MissingFile) => "missing file",
+        (GroupBy::Rule, None, _) | (_, _, Severity::ParseError) => "parse error",
+        (GroupBy::Severity, _, Severity::Critical) => "critical",
+        (GroupBy::Severity, _, Severity::Warning) => "warning",
+    }
+}
+
+// Prints the diagnostics under a header per group. Files keep the order they were linted in,
+// rules are sorted by name and severities from the most severe one, the diagnostics of a group
+// keeping the order of their files.
+fn print_grouped_results(
+    out: &mut dyn Write,
+    results: &[LintResult],
+    group_by: GroupBy,
+    show_status: bool,
+    verbose: bool,
+    error_format: Option<&str>,
+    wrap_width: Option<usize>,
+) -> io::Result<()> {
+    if show_status {
+        for result in results {
+            print_file_status(out, result)?;
+        }
+    }
+
+    let mut diagnostics = collect_diagnostics(results);
+    match group_by {
+        GroupBy::File => (),
+        GroupBy::Rule => {
+            diagnostics.sort_by(|a, b| get_group(a, group_by).cmp(get_group(b, group_by)))
+        }
+        GroupBy::Severity => {
+            diagnostics.sort_by_key(|diagnostic| std::cmp::Reverse(diagnostic.severity))
+        }
+    }
+
+    for group in diagnostics.chunk_by(|a, b| get_group(a, group_by) == get_group(b, group_by)) {
+        writeln!(
+            out,
+            "{}",
+            format!("{} ({})", get_group(&group[0], group_by), group.len()).bold()
+        )?;
+
+        for diagnostic in group {
+            if let Some(template) = error_format {
+                writeln!(out, "{}", report::template::format(template, diagnostic))?;
+                continue;
+            }
+
+            let head = match (&diagnostic.check_id, diagnostic.severity) {
+                (Some(check_id), _) => check_id,
+                (None, Severity::MissingFile) => "Missing file",
+                (None, _) => "Parse error",
+            };
+            let header = match diagnostic.severity {
+                Severity::Warning => validation::warning_header(head),
+                Severity::Critical | Severity::ParseError | Severity::MissingFile => {
+                    validation::error_header(head)
+                }
+            };
+            writeln!(
+                out,
+                "{} - {}",
+                header,
+                wrap_message(&diagnostic.message, head, wrap_width)
+            )?;
+            if diagnostic.check_id.is_some() {
+                writeln!(out, "  path: {}", diagnostic.instance_path)?;
+            }
+            if group_by != GroupBy::File {
+                writeln!(out, "  file: {}", diagnostic.file)?;
+            }
+            if let (true, Some(rule)) = (verbose, &diagnostic.rule) {
+                writeln!(out, "  rule: {}", rule)?;
+            }
+            writeln!(out)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_catalog_diagnostic(diagnostic: &CatalogDiagnostic) {
+    let head = diagnostic.check_id.as_ref().unwrap_or(&diagnostic.group);
+    let header = match diagnostic.severity {
+        CatalogSeverity::Warning => validation::warning_header(head),
+        CatalogSeverity::Critical => validation::error_header(head),
+    };
+
+    println!("{} - {}", header, diagnostic.message);
+    println!("  group: {}\n", diagnostic.group);
+}
+
+// The checks of a catalog directory, sorted by path, along with the files which can't be parsed
+// and their error.
+struct ParsedCatalog {
+    checks: Vec<(String, Check)>,
+    errors: Vec<(String, String)>,
+}
+
+fn parse_catalog(directory: &str) -> Result<ParsedCatalog, String> {
+    let mut files: Vec<String> = scan_directory(directory, false)
+        .map_err(|error| format!("unable to scan {}: {}", directory, error))?
+        .into_iter()
+        .filter(|check_path| is_yaml_file(check_path))
+        .collect();
+    files.sort();
+
+    let mut catalog = ParsedCatalog {
+        checks: vec![],
+        errors: vec![],
+    };
+    for file in files {
+        let check = get_input(Some(file.clone())).and_then(|input| {
+            serde_yaml::from_str::<Check>(&input)
+                .map_err(|error| validation::format_parse_error(&error))
+        });
+
+        match check {
+            Ok(check) => catalog.checks.push((file, check)),
+            Err(error) => catalog.errors.push((file, error)),
+        }
+    }
+
+    Ok(catalog)
+}
+
+fn lint_catalog(directory: &str, approved_groups: &[String]) -> bool {
+    let ParsedCatalog { checks, errors } = match parse_catalog(directory) {
+        Ok(catalog) => catalog,
+        Err(error) => {
+            println!("{} - {}", validation::error_header("Invalid input"), error);
+            return false;
+        }
+    };
+    for (file, error) in &errors {
+        println!(
+            "{} - {}: {}",
+            validation::error_header("Parse error"),
+            file,
+            error
+        );
+    }
+
+    let checks: Vec<Check> = checks.into_iter().map(|(_, check)| check).collect();
+    let diagnostics = validation::validate_catalog(&checks, approved_groups);
+    diagnostics.iter().for_each(print_catalog_diagnostic);
+
+    errors.is_empty()
+        && !diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == CatalogSeverity::Critical)
+}
+
+#[derive(Serialize)]
+struct InventoryCheck<'a> {
+    id: &'a str,
+    file: &'a str,
+    facts: &'a [FactDeclaration],
+}
+
+#[derive(Serialize)]
+struct Inventory<'a> {
+    checks: Vec<InventoryCheck<'a>>,
+    gatherers: Vec<&'a str>,
+}
+
+fn print_inventory(directory: &str, format: InventoryFormat) -> bool {
+    let ParsedCatalog { checks, errors } = match parse_catalog(directory) {
+        Ok(catalog) => catalog,
+        Err(error) => {
+            eprintln!("{} - {}", validation::error_header("Invalid input"), error);
+            return false;
+        }
+    };
+    for (file, error) in &errors {
+        eprintln!(
+            "{} - {}: {}",
+            validation::error_header("Parse error"),
+            file,
+            error
+        );
+    }
+
+    let mut gatherers: Vec<&str> = checks
+        .iter()
+        .flat_map(|(_, check)| check.facts.iter().map(|fact| fact.gatherer.as_str()))
+        .collect();
+    gatherers.sort();
+    gatherers.dedup();
+    let inventory = Inventory {
+        checks: checks
+            .iter()
+            .map(|(file, check)| InventoryCheck {
+                id: &check.id,
+                file,
+                facts: &check.facts,
+            })
+            .collect(),
+        gatherers,
+    };
+
+    match format {
+        InventoryFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&inventory).expect("the inventory should be serializable")
+        ),
+        InventoryFormat::Human => {
+            for check in &inventory.checks {
+                println!("{} - {}", check.id.bold(), check.file);
+                for fact in check.facts {
+                    match &fact.argument {
+                        Some(argument) => {
+                            println!("  {}: {} {}", fact.name, fact.gatherer, argument)
+                        }
+                        None => println!("  {}: {}", fact.name, fact.gatherer),
+                    }
+                }
+            }
+            println!("gatherers: {}", inventory.gatherers.join(", "));
+        }
+    }
+
+    errors.is_empty()
+}
+
+fn format_counts(counts: &Counts) -> String {
+    format!(
+        "files checked: {}, errors: {}, warnings: {}, parse errors: {}, missing files: {}",
+        counts.files_checked,
+        counts.errors,
+        counts.warnings,
+        counts.parse_errors,
+        counts.missing_files
+    )
+}
+
+fn print_counts(
+    out: &mut dyn Write,
+    counts: &Counts,
+    format: OutputFormat,
+    json_style: JsonStyle,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => writeln!(out, "{}", report::json::render_counts(counts, json_style)),
+        _ => writeln!(out, "{}", format_counts(counts)),
+    }
+}
+
+fn write_report(
+    out: &mut dyn Write,
+    results: &[LintResult],
+    args: &LintArgs,
+    verbose: bool,
+    wrap_width: Option<usize>,
+) -> io::Result<()> {
+    let json_style = match args.compact {
+        true => JsonStyle::Compact,
+        false => JsonStyle::Pretty,
+    };
+    let versions = Versions::new(args.schema_url.as_deref().unwrap_or(&args.schema_version));
+    let show_status = verbose && !args.quiet;
+
+    match args.format {
+        _ if args.count_only => {
+            let counts = Counts::from_diagnostics(&collect_diagnostics(results), results.len());
+            print_counts(out, &counts, args.format, json_style)
+        }
+        OutputFormat::Human => match args.group_by {
+            Some(group_by) => print_grouped_results(
+                out,
+                results,
+                group_by,
+                show_status,
+                verbose,
+                args.error_format.as_deref(),
+                wrap_width,
+            ),
+            None => print_results(
+                out,
+                results,
+                show_status,
+                verbose,
+                args.deprecations_only,
+                args.error_format.as_deref(),
+                wrap_width,
+            ),
+        },
+        OutputFormat::Json => writeln!(
+            out,
+            "{}",
+            report::json::render(
+                &collect_diagnostics(results),
+                results.len(),
+                &versions,
+                json_style
+            )
+        ),
+        OutputFormat::Codeclimate => writeln!(
+            out,
+            "{}",
+            report::codeclimate::render(&collect_diagnostics(results), json_style)
+        ),
+        OutputFormat::Sarif => writeln!(
+            out,
+            "{}",
+            report::sarif::render(
+                &collect_diagnostics(results),
+                &args.docs_base_url,
+                &versions,
+                json_style
+            )
+        ),
+        OutputFormat::Junit => {
+            let linted_files: Vec<LintedFile> = results
+                .iter()
+                .map(|result| LintedFile {
+                    file: &result.file,
+                    expectations: result
+                        .check
+                        .iter()
+                        .flat_map(|check| &check.expectations)
+                        .map(|expectation| expectation.name.as_str())
+                        .collect(),
+                    diagnostics: result.diagnostics(),
+                })
+                .collect();
+            let granularity = match args.junit_granularity {
+                JunitGranularity::File => Granularity::File,
+                JunitGranularity::Expectation => Granularity::Expectation,
+            };
+
+            writeln!(out, "{}", report::junit::render(&linted_files, granularity))
+        }
+    }
+}
+
+// Writes the report to the output file, if any, keeping the file statuses and a short summary on
+// the terminal.
+fn output_report(results: &[LintResult], args: &LintArgs) -> io::Result<()> {
+    let output = match &args.output {
+        Some(output) => output,
+        None => {
+            return write_report(
+                &mut io::stdout(),
+                results,
+                args,
+                args.verbose,
+                get_wrap_width(args),
+            )
+        }
+    };
 
-                process::exit(exit_code);
+    if args.verbose && !args.quiet {
+        let mut stdout = io::stdout();
+        for result in results {
+            print_file_status(&mut stdout, result)?;
+        }
+    }
+
+    let colorize = colored::control::SHOULD_COLORIZE.should_colorize();
+    colored::control::set_override(false);
+    let written = File::create(output)
+        .and_then(|mut file| write_report(&mut file, results, args, false, None));
+    colored::control::set_override(colorize);
+    written?;
+
+    let counts = Counts::from_diagnostics(&collect_diagnostics(results), results.len());
+    eprintln!("{} - report written to {}", format_counts(&counts), output);
+
+    Ok(())
+}
+
+fn write_summary(path: &str, results: &[LintResult], rules: &[EnabledValidator]) -> io::Result<()> {
+    let counts = Counts::from_diagnostics(&collect_diagnostics(results), results.len());
+    let rules: Vec<&str> = rules.iter().map(EnabledValidator::name).collect();
+
+    fs::write(
+        path,
+        report::json::render_summary(&counts, &rules, JsonStyle::Pretty),
+    )
+}
+
+fn evaluate_check(check_file: &str, sample_file: &str, engine: &Engine) -> bool {
+    let check: Check = match serde_yaml::from_str(&get_input_or_exit(Some(check_file.to_string())))
+    {
+        Ok(check) => check,
+        Err(error) => {
+            println!(
+                "{} - {}",
+                validation::error_header("Parse error"),
+                validation::format_parse_error(&error)
+            );
+            return false;
+        }
+    };
+    let sample: serde_json::Value =
+        match serde_json::from_str(&get_input_or_exit(Some(sample_file.to_string()))) {
+            Ok(sample) => sample,
+            Err(error) => {
+                println!("{} - {}", validation::error_header("Invalid sample"), error);
+                return false;
+            }
+        };
+
+    let mut is_ok = true;
+    for result in evaluation::evaluate_expectations(&check, &sample, engine) {
+        match result.outcome {
+            Ok(value) => println!("{} {}: {}", "✓".green(), result.name, value),
+            Err(diagnostic) => {
+                println!("{} {}", "✗".red(), result.name);
+                print_diagnostic(&mut io::stdout(), &diagnostic, None, None, None)
+                    .expect("Unable to write the evaluation results");
+                is_ok = false;
             }
+        }
+    }
+
+    is_ok
+}
+
+#[derive(Serialize)]
+struct ExitCodes {
+    warning: i32,
+    error: i32,
+    parse_error: i32,
+}
+
+/// The settings a lint runs with, as combined from the flags and their defaults.
+#[derive(Serialize)]
+struct EffectiveConfig<'a> {
+    rules: Vec<&'static str>,
+    format: &'static str,
+    schema: &'a str,
+    expectation_name_pattern: &'a str,
+    allowed_groups: Option<&'a [String]>,
+    allowed_env_keys: Option<&'a [String]>,
+    raw_schema_errors: bool,
+    schema_deprecations: bool,
+    max_message_length: usize,
+    min_value: f64,
+    exit_codes: ExitCodes,
+    cache: Option<&'a str>,
+    exec: Option<&'a str>,
+}
+
+fn print_config(args: &LintArgs, rules: &[EnabledValidator], options: &ValidationOptions) {
+    let format = match args.format {
+        OutputFormat::Human => "human",
+        OutputFormat::Json => "json",
+        OutputFormat::Codeclimate => "codeclimate",
+        OutputFormat::Junit => "junit",
+        OutputFormat::Sarif => "sarif",
+    };
+    let config = EffectiveConfig {
+        rules: rules.iter().map(EnabledValidator::name).collect(),
+        format,
+        schema: args.schema_url.as_deref().unwrap_or(&args.schema_version),
+        expectation_name_pattern: options.expectation_name_pattern.as_str(),
+        allowed_groups: options.allowed_groups.as_deref(),
+        allowed_env_keys: options.allowed_env_keys.as_deref(),
+        raw_schema_errors: options.raw_schema_errors,
+        schema_deprecations: options.schema_deprecations,
+        max_message_length: options.max_message_length,
+        min_value: options.min_value,
+        exit_codes: ExitCodes {
+            warning: get_warning_exit_code(args),
+            error: args.error_exit,
+            parse_error: args.parse_error_exit,
         },
+        cache: args.cache.as_deref(),
+        exec: args.exec.as_deref(),
+    };
+
+    if args.format == OutputFormat::Json {
+        let json = match args.compact {
+            true => serde_json::to_string(&config),
+            false => serde_json::to_string_pretty(&config),
+        };
+        println!(
+            "{}",
+            json.expect("the configuration should be serializable")
+        );
+        return;
+    }
+
+    let optional = |value: Option<&str>| value.unwrap_or("none").to_string();
+    println!("rules: {}", config.rules.join(", "));
+    println!("format: {}", config.format);
+    println!("schema: {}", config.schema);
+    println!(
+        "expectation name pattern: {}",
+        config.expectation_name_pattern
+    );
+    println!(
+        "allowed groups: {}",
+        config
+            .allowed_groups
+            .map_or("any".to_string(), |groups| groups.join(", "))
+    );
+    println!(
+        "allowed env keys: {}",
+        config
+            .allowed_env_keys
+            .map_or("any".to_string(), |env_keys| env_keys.join(", "))
+    );
+    println!("raw schema errors: {}", config.raw_schema_errors);
+    println!("schema deprecations: {}", config.schema_deprecations);
+    println!("max message length: {}", config.max_message_length);
+    println!("min value: {}", config.min_value);
+    println!(
+        "exit codes: warning {}, error {}, parse error {}",
+        config.exit_codes.warning, config.exit_codes.error, config.exit_codes.parse_error
+    );
+    println!("cache: {}", optional(config.cache));
+    println!("exec: {}", optional(config.exec));
+}
+
+// Warnings only fail the run when opted in, with a dedicated exit code or with --strict.
+fn get_warning_exit_code(args: &LintArgs) -> i32 {
+    match args.warn_exit_code {
+        Some(warn_exit_code) => warn_exit_code,
+        None if args.strict => 1,
+        None => 0,
+    }
+}
+
+// The exit code is the highest of the exit codes of the kinds of diagnostics found.
+fn get_exit_code(results: &[LintResult], args: &LintArgs) -> i32 {
+    let warning_exit = get_warning_exit_code(args);
+
+    results
+        .iter()
+        .map(|result| match &result.outcome {
+            Err(_) if result.is_allowed_parse_error() && args.strict => warning_exit,
+            Err(_) if result.is_allowed_parse_error() => 0,
+            Err(_) => args.parse_error_exit,
+            Ok(diagnostics) => diagnostics
+                .iter()
+                .map(|rule_diagnostic| match rule_diagnostic.diagnostic {
+                    ValidationDiagnostic::Critical { .. } => args.error_exit,
+                    ValidationDiagnostic::Warning { .. } => warning_exit,
+                })
+                .max()
+                .unwrap_or(0),
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+// Logs to stderr at the level selected with the -v flags, nothing being logged by default.
+fn init_logging(verbosity: u8) {
+    let level = match verbosity {
+        0 => return,
+        1 => tracing::Level::INFO,
+        2 => tracing::Level::DEBUG,
+        _ => tracing::Level::TRACE,
+    };
+
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(io::stderr)
+        .init();
+}
+
+fn set_color(choice: ColorChoice) {
+    match choice {
+        ColorChoice::Always => colored::control::set_override(true),
+        ColorChoice::Auto => colored::control::unset_override(),
+        ColorChoice::Never => colored::control::set_override(false),
+    }
+}
+
+fn main() -> Result<(), serde_yaml::Error> {
+    let args = Args::parse();
+    init_logging(args.log_verbosity);
+    let color = args.color;
+    set_color(color.unwrap_or(ColorChoice::Auto));
+
+    match args.command {
+        Commands::Lint(mut args) => {
+            if color.is_none() && (args.reproducible || args.no_color) {
+                set_color(ColorChoice::Never);
+            }
+            if args.fast {
+                args.rules = vec![EnabledValidator::Schema.name().to_string()];
+            }
+
+            let targets: Vec<String> = args
+                .file
+                .iter()
+                .chain(args.targets.iter())
+                .map(|target| match args.expand_env {
+                    true => expand_env(target).unwrap_or_else(|error| {
+                        eprintln!("{} - {}", validation::error_header("Invalid path"), error);
+                        process::exit(1);
+                    }),
+                    false => target.clone(),
+                })
+                .collect();
+            let is_single_file = args.files_from.is_none()
+                && args.manifest.is_none()
+                && targets.len() <= 1
+                && !is_directory(targets.first().cloned());
+            let mut files: Vec<Option<String>> = match (&args.files_from, targets.is_empty()) {
+                (Some(files_from), _) => read_list(files_from).into_iter().map(Some).collect(),
+                (None, true) if args.manifest.is_some() => {
+                    let manifest = args.manifest.as_deref().unwrap_or_default();
+                    match read_manifest(manifest) {
+                        Ok(files) => files.into_iter().map(Some).collect(),
+                        Err(error) => {
+                            eprintln!(
+                                "{} - {}",
+                                validation::error_header("Invalid manifest"),
+                                error
+                            );
+                            process::exit(1);
+                        }
+                    }
+                }
+                (None, true) => vec![None],
+                (None, false) => targets
+                    .iter()
+                    .flat_map(|target| resolve_target(target, &args.ignores, args.include_hidden))
+                    .map(Some)
+                    .collect(),
+            };
+            if args.reproducible {
+                files.sort();
+            }
+
+            let mut rules = normalize_rules(&args.rules);
+            if args.strict_schema && !rules.contains(&EnabledValidator::StrictSchema) {
+                rules.push(EnabledValidator::StrictSchema);
+            }
+            if args.groups.is_some() && !rules.contains(&EnabledValidator::Group) {
+                rules.push(EnabledValidator::Group);
+            }
+            if args.env_keys.is_some() && !rules.contains(&EnabledValidator::EnvKey) {
+                rules.push(EnabledValidator::EnvKey);
+            }
+            if args.deprecations_only {
+                rules = vec![EnabledValidator::Deprecation];
+            }
+            let options = ValidationOptions {
+                expectation_name_pattern: args.expectation_name_pattern.clone(),
+                allowed_groups: args.groups.as_deref().map(read_list),
+                allowed_env_keys: args.env_keys.as_deref().map(read_list),
+                raw_schema_errors: args.raw_schema_errors,
+                schema_deprecations: !args.no_deprecation_warnings,
+                max_message_length: args.max_message_length,
+                min_value: args.min_value,
+            };
+            if args.print_config {
+                print_config(&args, &rules, &options);
+                process::exit(0);
+            }
+
+            let (raw_schema, json_schema) = match &args.schema_url {
+                Some(url) => fetch_schema(url, args.cache.as_deref()).unwrap_or_else(|error| {
+                    eprintln!("{} - {}", validation::error_header("Invalid schema"), error);
+                    process::exit(1);
+                }),
+                None => (
+                    validation::SCHEMAS
+                        .iter()
+                        .find(|(version, _)| *version == args.schema_version)
+                        .map_or("", |(_, schema)| schema)
+                        .to_string(),
+                    validation::get_versioned_json_schema(&args.schema_version)
+                        .expect("the selected schema version should be bundled"),
+                ),
+            };
+            let cache = match &args.cache {
+                Some(directory) => {
+                    let settings = cache_settings(&raw_schema, &rules, &options);
+                    match Cache::open(directory, settings) {
+                        Ok(cache) => Some(cache),
+                        Err(error) => {
+                            eprintln!("Unable to open the cache in {}: {}", directory, error);
+                            process::exit(1);
+                        }
+                    }
+                }
+                None => None,
+            };
+            let base = Path::new(args.relative_to.as_deref().unwrap_or("."));
+            let engine = OnceCell::new();
+            let mut results: Vec<LintResult> = match &args.inline {
+                Some(inline) => vec![lint_input(
+                    "<inline>".to_string(),
+                    Ok(inline.clone()),
+                    &json_schema,
+                    &engine,
+                    &rules,
+                    &options,
+                    cache.as_ref(),
+                )],
+                None => files
+                    .into_iter()
+                    .map(|file| {
+                        let is_stdin = file.is_none();
+                        if let (Some(path), Some(_)) = (&file, &args.manifest) {
+                            if !Path::new(path).is_file() {
+                                return LintResult {
+                                    file: relative_path(path, base),
+                                    check: None,
+                                    outcome: Err(LintError::MissingFile(
+                                        "file listed in the manifest is not found".to_string(),
+                                    )),
+                                    parse_error_allowed: false,
+                                };
+                            }
+                        }
+                        let mut result = lint_file(
+                            file,
+                            &json_schema,
+                            &engine,
+                            &rules,
+                            &options,
+                            cache.as_ref(),
+                            args.exec.as_deref(),
+                        );
+                        match (is_stdin, &args.stdin_filename) {
+                            (true, Some(stdin_filename)) => result.file = stdin_filename.clone(),
+                            (true, None) => (),
+                            (false, _) => result.file = relative_path(&result.file, base),
+                        }
+
+                        result
+                    })
+                    .collect(),
+            };
+            results
+                .iter_mut()
+                .for_each(|result| result.parse_error_allowed = args.allow_parse_errors);
+
+            output_report(&results, &args).expect("Unable to write the lint results");
+            if let Some(summary_json) = &args.summary_json {
+                write_summary(summary_json, &results, &rules).expect("Unable to write the summary");
+            }
+
+            let exit_code = get_exit_code(&results, &args);
+
+            if args.show && is_single_file && args.format == OutputFormat::Human {
+                let check = results
+                    .into_iter()
+                    .find(LintResult::is_ok)
+                    .and_then(|result| result.check);
+
+                if let Some(check) = check {
+                    display::print_check(check);
+                }
+            }
+
+            process::exit(exit_code);
+        }
 
         Commands::Show { file } => {
-            let input = get_input(file);
+            let input = get_input_or_exit(file);
 
             let check: Check = serde_yaml::from_str(&input)?;
 
             display::print_check(check);
         }
+
+        Commands::Eval { check, facts } => {
+            let exit_code = match evaluate_check(&check, &facts, &engine::get_engine()) {
+                true => 0,
+                false => 1,
+            };
+
+            process::exit(exit_code);
+        }
+
+        Commands::Catalog {
+            directory,
+            approved_groups,
+        } => {
+            let exit_code = match lint_catalog(&directory, &approved_groups) {
+                true => 0,
+                false => 1,
+            };
+
+            process::exit(exit_code);
+        }
+
+        Commands::Inventory { directory, format } => {
+            let exit_code = match print_inventory(&directory, format) {
+                true => 0,
+                false => 1,
+            };
+
+            process::exit(exit_code);
+        }
+
+        Commands::NormalizeId { file, write } => {
+            let normalized = id::normalize_id(&get_input_or_exit(Some(file.clone())));
+
+            match write {
+                true => fs::write(&file, normalized).expect("Unable to write file"),
+                false => print!("{}", normalized),
+            }
+        }
+
+        Commands::Completions { shell } => {
+            clap_complete::generate(shell, &mut Args::command(), "tlint", &mut io::stdout());
+        }
     }
 
     Ok(())
@@ -0,0 +1,154 @@
+use super::{to_json, DiagnosticOutput, JsonStyle, Severity};
+use crate::fnv;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Issue {
+    #[serde(rename = "type")]
+    issue_type: &'static str,
+    check_name: &'static str,
+    description: String,
+    severity: &'static str,
+    fingerprint: String,
+    location: Location,
+}
+
+#[derive(Serialize)]
+struct Location {
+    path: String,
+    lines: Lines,
+}
+
+#[derive(Serialize)]
+struct Lines {
+    begin: usize,
+}
+
+// The fingerprints of the reported issues stay the same between tlint builds.
+fn fingerprint(diagnostic: &DiagnosticOutput) -> String {
+    let fields = [
+        diagnostic.file.as_str(),
+        diagnostic.check_id.as_deref().unwrap_or_default(),
+        diagnostic.rule.as_deref().unwrap_or_default(),
+        &diagnostic.instance_path,
+        &diagnostic.message,
+    ];
+
+    format!("{:016x}", fnv::hash(&fields))
+}
+
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warning => "minor",
+        Severity::Critical => "major",
+        Severity::ParseError | Severity::MissingFile => "blocker",
+    }
+}
+
+fn description(diagnostic: &DiagnosticOutput) -> String {
+    match (&diagnostic.check_id, diagnostic.instance_path.is_empty()) {
+        (Some(check_id), false) => format!(
+            "{} - {} (path: {})",
+            check_id, diagnostic.message, diagnostic.instance_path
+        ),
+        (Some(check_id), true) => format!("{} - {}", check_id, diagnostic.message),
+        (None, _) if diagnostic.severity == Severity::MissingFile => {
+            format!("Missing file - {}", diagnostic.message)
+        }
+        (None, _) => format!("Parse error - {}", diagnostic.message),
+    }
+}
+
+/// Renders the diagnostics as a Code Climate report, as consumed by GitLab code quality.
+pub fn render(diagnostics: &[DiagnosticOutput], style: JsonStyle) -> String {
+    let issues: Vec<Issue> = diagnostics
+        .iter()
+        .map(|diagnostic| Issue {
+            issue_type: "issue",
+            check_name: "tlint",
+            description: description(diagnostic),
+            severity: severity(diagnostic.severity),
+            fingerprint: fingerprint(diagnostic),
+            location: Location {
+                path: diagnostic.file.to_string(),
+                lines: Lines { begin: 1 },
+            },
+        })
+        .collect();
+
+    to_json(&issues, style).expect("a Code Climate report should be serializable")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_codeclimate_report() {
+        let diagnostics = vec![
+            DiagnosticOutput {
+                file: "checks/156F64.yaml".to_string(),
+                check_id: Some("156F64".to_string()),
+                group: None,
+                rule: None,
+                severity: Severity::Warning,
+                message: "Property 'premium' is deprecated and will be removed in the future"
+                    .to_string(),
+                instance_path: "/premium".to_string(),
+            },
+            DiagnosticOutput::from_parse_error("checks/invalid.yaml", "missing field `id`"),
+        ];
+
+        let report: serde_json::Value =
+            serde_json::from_str(&render(&diagnostics, JsonStyle::Pretty))
+                .expect("the report should be JSON");
+        let issues = report.as_array().unwrap();
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[0]["description"],
+            "156F64 - Property 'premium' is deprecated and will be removed in the future (path: /premium)"
+        );
+        assert_eq!(issues[0]["severity"], "minor");
+        assert_eq!(issues[0]["location"]["path"], "checks/156F64.yaml");
+        assert_eq!(issues[0]["location"]["lines"]["begin"], 1);
+        assert_eq!(issues[0]["fingerprint"], "a32820bd4eb3a114");
+        assert_eq!(issues[1]["severity"], "blocker");
+        assert_ne!(issues[0]["fingerprint"], issues[1]["fingerprint"]);
+    }
+
+    #[test]
+    fn render_missing_file() {
+        let diagnostics = vec![DiagnosticOutput::from_missing_file(
+            "checks/missing.yaml",
+            "file listed in the manifest is not found",
+        )];
+
+        let report: serde_json::Value =
+            serde_json::from_str(&render(&diagnostics, JsonStyle::Pretty))
+                .expect("the report should be JSON");
+        let issues = report.as_array().unwrap();
+
+        assert_eq!(
+            issues[0]["description"],
+            "Missing file - file listed in the manifest is not found"
+        );
+        assert_eq!(issues[0]["severity"], "blocker");
+        assert_eq!(issues[0]["location"]["path"], "checks/missing.yaml");
+    }
+
+    #[test]
+    fn fingerprint_identical_diagnostics_of_different_files() {
+        let diagnostics = vec![
+            DiagnosticOutput::from_parse_error("checks/first.yaml", "missing field `id`"),
+            DiagnosticOutput::from_parse_error("checks/second.yaml", "missing field `id`"),
+        ];
+
+        let report: serde_json::Value =
+            serde_json::from_str(&render(&diagnostics, JsonStyle::Pretty))
+                .expect("the report should be JSON");
+        let issues = report.as_array().unwrap();
+
+        assert_ne!(issues[0]["fingerprint"], issues[1]["fingerprint"]);
+    }
+}
@@ -0,0 +1,187 @@
+use super::{to_json, Counts, DiagnosticOutput, JsonStyle, Versions};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    #[serde(flatten)]
+    versions: &'a Versions<'a>,
+    ok: bool,
+    files_checked: usize,
+    diagnostics: &'a [DiagnosticOutput],
+}
+
+/// Renders the diagnostics as a JSON object. The object is emitted even when there are no
+/// diagnostics, so that consumers can tell a clean run from a run which did not happen.
+pub fn render(
+    diagnostics: &[DiagnosticOutput],
+    files_checked: usize,
+    versions: &Versions,
+    style: JsonStyle,
+) -> String {
+    let report = JsonReport {
+        versions,
+        ok: diagnostics.is_empty(),
+        files_checked,
+        diagnostics,
+    };
+
+    to_json(&report, style).expect("a JSON report should be serializable")
+}
+
+#[derive(Serialize)]
+struct SummaryReport<'a> {
+    ok: bool,
+    #[serde(flatten)]
+    counts: &'a Counts,
+    rules: &'a [&'a str],
+}
+
+/// Renders the totals of a lint run along with the rules it ran, as a summary of a report
+/// rendered in another format.
+pub fn render_summary(counts: &Counts, rules: &[&str], style: JsonStyle) -> String {
+    let report = SummaryReport {
+        ok: counts.errors + counts.warnings + counts.parse_errors + counts.missing_files == 0,
+        counts,
+        rules,
+    };
+
+    to_json(&report, style).expect("a JSON summary should be serializable")
+}
+
+/// Renders only the totals of a lint run as a JSON object.
+pub fn render_counts(counts: &Counts, style: JsonStyle) -> String {
+    to_json(counts, style).expect("the counts should be serializable")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Severity;
+
+    #[test]
+    fn render_clean_report() {
+        let report: serde_json::Value =
+            serde_json::from_str(&render(&[], 3, &Versions::new("main"), JsonStyle::Pretty))
+                .expect("the report should be JSON");
+
+        assert_eq!(
+            report,
+            serde_json::json!({
+                "tlint_version": env!("CARGO_PKG_VERSION"),
+                "schema_version": "main",
+                "ok": true,
+                "files_checked": 3,
+                "diagnostics": []
+            })
+        );
+    }
+
+    #[test]
+    fn render_report_with_diagnostics() {
+        let diagnostics = vec![DiagnosticOutput {
+            file: "checks/156F64.yaml".to_string(),
+            check_id: Some("156F64".to_string()),
+            group: Some("Corosync".to_string()),
+            rule: Some("deprecation".to_string()),
+            severity: Severity::Warning,
+            message: "Property 'premium' is deprecated and will be removed in the future"
+                .to_string(),
+            instance_path: "/premium".to_string(),
+        }];
+
+        let report: serde_json::Value = serde_json::from_str(&render(
+            &diagnostics,
+            1,
+            &Versions::new("main"),
+            JsonStyle::Pretty,
+        ))
+        .expect("the report should be JSON");
+
+        assert_eq!(report["ok"], false);
+        assert_eq!(report["files_checked"], 1);
+        assert_eq!(report["diagnostics"][0]["severity"], "warning");
+        assert_eq!(report["diagnostics"][0]["instance_path"], "/premium");
+        assert_eq!(report["diagnostics"][0]["rule"], "deprecation");
+        assert_eq!(report["diagnostics"][0]["group"], "Corosync");
+    }
+
+    #[test]
+    fn render_counts_report() {
+        let diagnostics = vec![
+            DiagnosticOutput::from_parse_error("checks/broken.yaml", "invalid type"),
+            DiagnosticOutput {
+                file: "checks/156F64.yaml".to_string(),
+                check_id: Some("156F64".to_string()),
+                group: None,
+                rule: Some("schema".to_string()),
+                severity: Severity::Critical,
+                message: "\"name\" is a required property".to_string(),
+                instance_path: "/expectations/0".to_string(),
+            },
+        ];
+
+        let counts = Counts::from_diagnostics(&diagnostics, 2);
+        let report: serde_json::Value =
+            serde_json::from_str(&render_counts(&counts, JsonStyle::Pretty))
+                .expect("the report should be JSON");
+
+        assert_eq!(
+            report,
+            serde_json::json!({
+                "files_checked": 2,
+                "errors": 1,
+                "warnings": 0,
+                "parse_errors": 1,
+                "missing_files": 0
+            })
+        );
+    }
+
+    #[test]
+    fn render_compact_report() {
+        let diagnostics = vec![DiagnosticOutput::from_parse_error(
+            "checks/broken.yaml",
+            "invalid type",
+        )];
+
+        let compact = render(&diagnostics, 1, &Versions::new("main"), JsonStyle::Compact);
+        let pretty = render(&diagnostics, 1, &Versions::new("main"), JsonStyle::Pretty);
+
+        assert_eq!(compact.lines().count(), 1);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&compact).expect("the report should be JSON"),
+            serde_json::from_str::<serde_json::Value>(&pretty).expect("the report should be JSON")
+        );
+    }
+
+    #[test]
+    fn render_summary_report() {
+        let counts = Counts {
+            files_checked: 2,
+            errors: 0,
+            warnings: 1,
+            parse_errors: 0,
+            missing_files: 0,
+        };
+
+        let report: serde_json::Value = serde_json::from_str(&render_summary(
+            &counts,
+            &["schema", "expectation"],
+            JsonStyle::Compact,
+        ))
+        .expect("the summary should be JSON");
+
+        assert_eq!(
+            report,
+            serde_json::json!({
+                "ok": false,
+                "files_checked": 2,
+                "errors": 0,
+                "warnings": 1,
+                "parse_errors": 0,
+                "missing_files": 0,
+                "rules": ["schema", "expectation"]
+            })
+        );
+    }
+}
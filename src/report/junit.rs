@@ -0,0 +1,211 @@
+use super::{DiagnosticOutput, Severity};
+
+/// What a JUnit testcase stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    File,
+    Expectation,
+}
+
+/// A linted file and the names of the expectations of its check, if it could be parsed.
+pub struct LintedFile<'a> {
+    pub file: &'a str,
+    pub expectations: Vec<&'a str>,
+    pub diagnostics: Vec<DiagnosticOutput>,
+}
+
+struct TestCase<'a> {
+    name: String,
+    classname: &'a str,
+    failures: Vec<&'a DiagnosticOutput>,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn failure_type(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warning => "warning",
+        Severity::Critical => "critical",
+        Severity::ParseError => "parse_error",
+        Severity::MissingFile => "missing_file",
+    }
+}
+
+fn expectation_index(instance_path: &str) -> Option<usize> {
+    instance_path
+        .strip_prefix("/expectations/")?
+        .split('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+// Diagnostics outside of the expectations, like parse errors or schema errors on other
+// properties, are reported in a testcase standing for the whole check.
+fn expectation_test_cases<'a>(linted_file: &'a LintedFile) -> Vec<TestCase<'a>> {
+    let check_failures = linted_file
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| {
+            expectation_index(&diagnostic.instance_path)
+                .is_none_or(|index| index >= linted_file.expectations.len())
+        })
+        .collect();
+
+    let mut test_cases = vec![TestCase {
+        name: format!("{} check", linted_file.file),
+        classname: linted_file.file,
+        failures: check_failures,
+    }];
+
+    test_cases.extend(
+        linted_file
+            .expectations
+            .iter()
+            .enumerate()
+            .map(|(index, expectation)| TestCase {
+                name: format!("{} {}", linted_file.file, expectation),
+                classname: linted_file.file,
+                failures: linted_file
+                    .diagnostics
+                    .iter()
+                    .filter(|diagnostic| {
+                        expectation_index(&diagnostic.instance_path) == Some(index)
+                    })
+                    .collect(),
+            }),
+    );
+
+    test_cases
+}
+
+fn render_test_case(test_case: &TestCase) -> String {
+    let mut xml = format!(
+        "    <testcase name=\"{}\" classname=\"{}\"",
+        escape(&test_case.name),
+        escape(test_case.classname)
+    );
+
+    if test_case.failures.is_empty() {
+        xml.push_str("/>\n");
+        return xml;
+    }
+
+    xml.push_str(">\n");
+    for failure in &test_case.failures {
+        xml.push_str(&format!(
+            "      <failure type=\"{}\" message=\"{}\">path: {}</failure>\n",
+            failure_type(failure.severity),
+            escape(&failure.message),
+            escape(&failure.instance_path)
+        ));
+    }
+    xml.push_str("    </testcase>\n");
+
+    xml
+}
+
+/// Renders the lint results as a JUnit XML report, with a testcase per file or per expectation.
+pub fn render(linted_files: &[LintedFile], granularity: Granularity) -> String {
+    let test_cases: Vec<TestCase> = linted_files
+        .iter()
+        .flat_map(|linted_file| match granularity {
+            Granularity::File => vec![TestCase {
+                name: linted_file.file.to_string(),
+                classname: linted_file.file,
+                failures: linted_file.diagnostics.iter().collect(),
+            }],
+            Granularity::Expectation => expectation_test_cases(linted_file),
+        })
+        .collect();
+
+    let failures = test_cases
+        .iter()
+        .filter(|test_case| !test_case.failures.is_empty())
+        .count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"tlint\" tests=\"{}\" failures=\"{}\">\n",
+        test_cases.len(),
+        failures
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"tlint\" tests=\"{}\" failures=\"{}\">\n",
+        test_cases.len(),
+        failures
+    ));
+    test_cases
+        .iter()
+        .for_each(|test_case| xml.push_str(&render_test_case(test_case)));
+    xml.push_str("  </testsuite>\n</testsuites>");
+
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn linted_file() -> LintedFile<'static> {
+        LintedFile {
+            file: "checks/156F64.yaml",
+            expectations: vec!["timeout", "consensus"],
+            diagnostics: vec![
+                DiagnosticOutput {
+                    file: "checks/156F64.yaml".to_string(),
+                    check_id: Some("156F64".to_string()),
+                    group: None,
+                    rule: None,
+                    severity: Severity::Critical,
+                    message: "function 'print' is not allowed".to_string(),
+                    instance_path: "/expectations/1".to_string(),
+                },
+                DiagnosticOutput {
+                    file: "checks/156F64.yaml".to_string(),
+                    check_id: Some("156F64".to_string()),
+                    group: None,
+                    rule: None,
+                    severity: Severity::Warning,
+                    message: "Property 'premium' is deprecated and will be removed in the future"
+                        .to_string(),
+                    instance_path: "/premium".to_string(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn render_file_test_cases() {
+        let report = render(&[linted_file()], Granularity::File);
+
+        assert!(report.contains("<testsuites name=\"tlint\" tests=\"1\" failures=\"1\">"));
+        assert!(report
+            .contains("<testcase name=\"checks/156F64.yaml\" classname=\"checks/156F64.yaml\">"));
+        assert!(report.contains(
+            "<failure type=\"critical\" message=\"function &apos;print&apos; is not allowed\">path: /expectations/1</failure>"
+        ));
+    }
+
+    #[test]
+    fn render_expectation_test_cases() {
+        let report = render(&[linted_file()], Granularity::Expectation);
+
+        assert!(report.contains("<testsuites name=\"tlint\" tests=\"3\" failures=\"2\">"));
+        assert!(report.contains(
+            "<testcase name=\"checks/156F64.yaml timeout\" classname=\"checks/156F64.yaml\"/>"
+        ));
+        assert!(report.contains(
+            "<testcase name=\"checks/156F64.yaml consensus\" classname=\"checks/156F64.yaml\">\n      <failure type=\"critical\""
+        ));
+        assert!(report.contains(
+            "<testcase name=\"checks/156F64.yaml check\" classname=\"checks/156F64.yaml\">\n      <failure type=\"warning\""
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::dsl::types::ValidationDiagnostic;
+use serde::Serialize;
+
+pub mod codeclimate;
+pub mod json;
+pub mod junit;
+pub mod sarif;
+pub mod template;
+
+/// Layout of the JSON based reports. Both styles serialize the same content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonStyle {
+    Pretty,
+    Compact,
+}
+
+fn to_json<T: Serialize>(value: &T, style: JsonStyle) -> serde_json::Result<String> {
+    match style {
+        JsonStyle::Pretty => serde_json::to_string_pretty(value),
+        JsonStyle::Compact => serde_json::to_string(value),
+    }
+}
+
+/// Versions of tlint and of the check definition schema a report was produced with.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Versions<'a> {
+    pub tlint_version: &'static str,
+    /// Bundled schema version, or URL of the schema validated against.
+    pub schema_version: &'a str,
+}
+
+impl<'a> Versions<'a> {
+    pub fn new(schema_version: &'a str) -> Versions<'a> {
+        Versions {
+            tlint_version: env!("CARGO_PKG_VERSION"),
+            schema_version,
+        }
+    }
+}
+
+/// Severity of a diagnostic, ordered from the least to the most severe.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Warning,
+    Critical,
+    ParseError,
+    MissingFile,
+}
+
+/// A diagnostic reported for a linted file, regardless of the output format.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct DiagnosticOutput {
+    pub file: String,
+    pub check_id: Option<String>,
+    /// Group of the check, none for parse errors and missing files.
+    pub group: Option<String>,
+    /// Rule which reported the diagnostic, none for parse errors and missing files.
+    pub rule: Option<String>,
+    pub severity: Severity,
+    pub message: String,
+    pub instance_path: String,
+}
+
+impl DiagnosticOutput {
+    pub fn from_validation(
+        file: &str,
+        rule: &str,
+        diagnostic: &ValidationDiagnostic,
+    ) -> DiagnosticOutput {
+        let (severity, check_id, message, instance_path) = match diagnostic {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => (Severity::Warning, check_id, message, instance_path),
+            ValidationDiagnostic::Critical {
+                check_id,
+                message,
+                instance_path,
+            } => (Severity::Critical, check_id, message, instance_path),
+        };
+
+        DiagnosticOutput {
+            file: file.to_string(),
+            check_id: Some(check_id.to_string()),
+            group: None,
+            rule: Some(rule.to_string()),
+            severity,
+            message: message.to_string(),
+            instance_path: instance_path.to_string(),
+        }
+    }
+
+    pub fn from_parse_error(file: &str, error: &str) -> DiagnosticOutput {
+        DiagnosticOutput {
+            file: file.to_string(),
+            check_id: None,
+            group: None,
+            rule: None,
+            severity: Severity::ParseError,
+            message: error.to_string(),
+            instance_path: String::new(),
+        }
+    }
+
+    pub fn from_missing_file(file: &str, error: &str) -> DiagnosticOutput {
+        DiagnosticOutput {
+            severity: Severity::MissingFile,
+            ..DiagnosticOutput::from_parse_error(file, error)
+        }
+    }
+}
+
+/// Totals of a lint run, reported instead of the diagnostics when only the numbers matter.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Counts {
+    pub files_checked: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub parse_errors: usize,
+    pub missing_files: usize,
+}
+
+impl Counts {
+    pub fn from_diagnostics(diagnostics: &[DiagnosticOutput], files_checked: usize) -> Counts {
+        let count = |severity: Severity| {
+            diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity == severity)
+                .count()
+        };
+
+        Counts {
+            files_checked,
+            errors: count(Severity::Critical),
+            warnings: count(Severity::Warning),
+            parse_errors: count(Severity::ParseError),
+            missing_files: count(Severity::MissingFile),
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use super::{to_json, DiagnosticOutput, JsonStyle, Severity, Versions};
+use crate::dsl::types::EnabledValidator;
+use serde::Serialize;
+
+/// Documentation the rule help URIs point to, the rule name being its fragment.
+pub const DEFAULT_DOCS_BASE_URL: &str =
+    "https://github.com/trento-project/wanda/blob/main/guides/specification.md";
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+const PARSE_ERROR_RULE: &str = "parse-error";
+
+const MISSING_FILE_RULE: &str = "missing-file";
+
+#[derive(Serialize)]
+struct Log<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [Run; 1],
+    /// SARIF only allows additional top-level fields in the property bag.
+    properties: &'a Versions<'a>,
+}
+
+#[derive(Serialize)]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    short_description: Text,
+    full_description: Text,
+    help_uri: String,
+}
+
+#[derive(Serialize)]
+struct Text {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: Text,
+    locations: [Location; 1],
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+}
+
+fn rule_id(diagnostic: &DiagnosticOutput) -> &str {
+    match (&diagnostic.rule, diagnostic.severity) {
+        (Some(rule), _) => rule,
+        (None, Severity::MissingFile) => MISSING_FILE_RULE,
+        (None, _) => PARSE_ERROR_RULE,
+    }
+}
+
+/// Composes the documentation URI of a rule from the docs base URL.
+pub fn help_uri(docs_base_url: &str, rule: &str) -> String {
+    format!("{}#{}", docs_base_url.trim_end_matches(['/', '#']), rule)
+}
+
+fn rule(id: &str, docs_base_url: &str) -> Rule {
+    let description = match EnabledValidator::from_name(id) {
+        Some(validator) => validator.description(),
+        None if id == PARSE_ERROR_RULE => "Reports the files which are not a valid check",
+        None if id == MISSING_FILE_RULE => "Reports the files which don't exist or can't be read",
+        None => "Reports the diagnostics of the command run with --exec",
+    };
+
+    Rule {
+        id: id.to_string(),
+        short_description: Text {
+            text: id.to_string(),
+        },
+        full_description: Text {
+            text: description.to_string(),
+        },
+        help_uri: help_uri(docs_base_url, id),
+    }
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warning => "warning",
+        Severity::Critical | Severity::ParseError | Severity::MissingFile => "error",
+    }
+}
+
+fn message(diagnostic: &DiagnosticOutput) -> String {
+    match (&diagnostic.check_id, diagnostic.instance_path.is_empty()) {
+        (Some(check_id), false) => format!(
+            "{} - {} (path: {})",
+            check_id, diagnostic.message, diagnostic.instance_path
+        ),
+        (Some(check_id), true) => format!("{} - {}", check_id, diagnostic.message),
+        (None, _) if diagnostic.severity == Severity::MissingFile => {
+            format!("Missing file - {}", diagnostic.message)
+        }
+        (None, _) => format!("Parse error - {}", diagnostic.message),
+    }
+}
+
+/// Renders the diagnostics as a SARIF log, as consumed by code scanning UIs. The rules of the
+/// driver are the ones which reported a diagnostic, sorted by name.
+pub fn render(
+    diagnostics: &[DiagnosticOutput],
+    docs_base_url: &str,
+    versions: &Versions,
+    style: JsonStyle,
+) -> String {
+    let mut rule_ids: Vec<&str> = diagnostics.iter().map(rule_id).collect();
+    rule_ids.sort();
+    rule_ids.dedup();
+
+    let results = diagnostics
+        .iter()
+        .map(|diagnostic| SarifResult {
+            rule_id: rule_id(diagnostic).to_string(),
+            rule_index: rule_ids
+                .binary_search(&rule_id(diagnostic))
+                .expect("the rule of a diagnostic should be listed"),
+            level: level(diagnostic.severity),
+            message: Text {
+                text: message(diagnostic),
+            },
+            locations: [Location {
+                physical_location: PhysicalLocation {
+                    artifact_location: ArtifactLocation {
+                        uri: diagnostic.file.to_string(),
+                    },
+                    region: Region { start_line: 1 },
+                },
+            }],
+        })
+        .collect();
+
+    let log = Log {
+        schema: SARIF_SCHEMA,
+        version: "2.1.0",
+        runs: [Run {
+            tool: Tool {
+                driver: Driver {
+                    name: "tlint",
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: "https://github.com/trento-project/tlint",
+                    rules: rule_ids.iter().map(|id| rule(id, docs_base_url)).collect(),
+                },
+            },
+            results,
+        }],
+        properties: versions,
+    };
+
+    to_json(&log, style).expect("a SARIF log should be serializable")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_sarif_log() {
+        let diagnostics = vec![
+            DiagnosticOutput {
+                file: "checks/156F64.yaml".to_string(),
+                check_id: Some("156F64".to_string()),
+                group: None,
+                rule: Some("schema".to_string()),
+                severity: Severity::Warning,
+                message: "Property 'premium' is deprecated and will be removed in the future"
+                    .to_string(),
+                instance_path: "/premium".to_string(),
+            },
+            DiagnosticOutput::from_parse_error("checks/invalid.yaml", "missing field `id`"),
+        ];
+
+        let log: serde_json::Value = serde_json::from_str(&render(
+            &diagnostics,
+            "https://docs.example.com/rules/",
+            &Versions::new("main"),
+            JsonStyle::Pretty,
+        ))
+        .expect("the log should be JSON");
+        let run = &log["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(log["properties"]["schema_version"], "main");
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "parse-error");
+        assert_eq!(rules[1]["helpUri"], "https://docs.example.com/rules#schema");
+        assert_eq!(
+            rules[1]["fullDescription"]["text"],
+            "Validates the check against the check definition schema"
+        );
+        assert_eq!(run["results"][0]["ruleId"], "schema");
+        assert_eq!(run["results"][0]["ruleIndex"], 1);
+        assert_eq!(run["results"][0]["level"], "warning");
+        assert_eq!(
+            run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "checks/156F64.yaml"
+        );
+        assert_eq!(run["results"][1]["level"], "error");
+    }
+
+    #[test]
+    fn render_missing_file() {
+        let diagnostics = vec![DiagnosticOutput::from_missing_file(
+            "checks/missing.yaml",
+            "file listed in the manifest is not found",
+        )];
+
+        let log: serde_json::Value = serde_json::from_str(&render(
+            &diagnostics,
+            DEFAULT_DOCS_BASE_URL,
+            &Versions::new("main"),
+            JsonStyle::Pretty,
+        ))
+        .expect("the log should be JSON");
+        let run = &log["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0]["id"], "missing-file");
+        assert_eq!(
+            rules[0]["fullDescription"]["text"],
+            "Reports the files which don't exist or can't be read"
+        );
+        assert_eq!(run["results"][0]["ruleId"], "missing-file");
+        assert_eq!(run["results"][0]["level"], "error");
+        assert_eq!(
+            run["results"][0]["message"]["text"],
+            "Missing file - file listed in the manifest is not found"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use super::{DiagnosticOutput, Severity};
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warning => "warning",
+        Severity::Critical => "critical",
+        Severity::ParseError => "parse_error",
+        Severity::MissingFile => "missing_file",
+    }
+}
+
+/// Formats a diagnostic with a template. The `{severity}`, `{check_id}`, `{path}`, `{message}`,
+/// `{file}` and `{line}` placeholders are substituted, any other text is kept as is. Diagnostics
+/// are not tracked back to the YAML source, so `{line}` is always 1.
+pub fn format(template: &str, diagnostic: &DiagnosticOutput) -> String {
+    let placeholders = [
+        ("{severity}", severity_name(diagnostic.severity)),
+        ("{check_id}", diagnostic.check_id.as_deref().unwrap_or("")),
+        ("{path}", &diagnostic.instance_path),
+        ("{message}", &diagnostic.message),
+        ("{file}", &diagnostic.file),
+        ("{line}", "1"),
+    ];
+
+    let mut formatted = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        formatted.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        match placeholders
+            .iter()
+            .find(|(placeholder, _)| rest.starts_with(placeholder))
+        {
+            Some((placeholder, value)) => {
+                formatted.push_str(value);
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                formatted.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    formatted.push_str(rest);
+
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_diagnostic() {
+        let diagnostic = DiagnosticOutput {
+            file: "checks/156F64.yaml".to_string(),
+            check_id: Some("156F64".to_string()),
+            group: None,
+            rule: None,
+            severity: Severity::Warning,
+            message: "Property 'premium' is deprecated and will be removed in the future"
+                .to_string(),
+            instance_path: "/premium".to_string(),
+        };
+
+        assert_eq!(
+            format(
+                "{file}:{line}: {severity} [{check_id}] {message} ({path}) {unknown}",
+                &diagnostic
+            ),
+            "checks/156F64.yaml:1: warning [156F64] Property 'premium' is deprecated and will be removed in the future (/premium) {unknown}"
+        );
+    }
+
+    #[test]
+    fn format_does_not_substitute_values() {
+        let diagnostic = DiagnosticOutput::from_parse_error("checks/broken.yaml", "bad {file}");
+
+        assert_eq!(
+            format("{severity}: {message}", &diagnostic),
+            "parse_error: bad {file}"
+        );
+    }
+}
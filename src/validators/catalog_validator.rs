@@ -0,0 +1,201 @@
+use crate::dsl::types::{CatalogDiagnostic, CatalogSeverity, CatalogValidator, Check};
+use std::collections::HashMap;
+
+pub struct UniqueIdValidator {}
+
+impl CatalogValidator for UniqueIdValidator {
+    fn validate(&self, checks: &[Check]) -> Vec<CatalogDiagnostic> {
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
+
+        checks
+            .iter()
+            .filter_map(|check| {
+                let count = occurrences.entry(&check.id).or_default();
+                *count += 1;
+
+                match *count {
+                    2 => Some(CatalogDiagnostic {
+                        severity: CatalogSeverity::Critical,
+                        check_id: Some(check.id.clone()),
+                        group: check.group.clone(),
+                        message: format!("check id '{}' is not unique", check.id),
+                    }),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Reports the names shared by checks with different ids, as a warning since names may
+/// legitimately overlap.
+pub struct UniqueNameValidator {}
+
+impl CatalogValidator for UniqueNameValidator {
+    fn validate(&self, checks: &[Check]) -> Vec<CatalogDiagnostic> {
+        let mut names: Vec<(&str, Vec<&Check>)> = Vec::new();
+        for check in checks {
+            match names.iter_mut().find(|(name, _)| *name == check.name) {
+                Some((_, named_checks)) => named_checks.push(check),
+                None => names.push((&check.name, vec![check])),
+            }
+        }
+
+        names
+            .into_iter()
+            .filter_map(|(name, named_checks)| {
+                let mut ids: Vec<&str> = Vec::new();
+                for check in &named_checks {
+                    if !ids.contains(&check.id.as_str()) {
+                        ids.push(&check.id);
+                    }
+                }
+
+                match ids.len() {
+                    0 | 1 => None,
+                    _ => Some(CatalogDiagnostic {
+                        severity: CatalogSeverity::Warning,
+                        check_id: Some(named_checks[0].id.clone()),
+                        group: named_checks[0].group.clone(),
+                        message: format!(
+                            "check name '{}' is shared by checks {}",
+                            name,
+                            ids.join(", ")
+                        ),
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+pub struct ApprovedGroupValidator<'a> {
+    pub groups: &'a [String],
+}
+
+impl<'a> CatalogValidator for ApprovedGroupValidator<'a> {
+    fn validate(&self, checks: &[Check]) -> Vec<CatalogDiagnostic> {
+        let unapproved_groups = checks
+            .iter()
+            .filter(|check| !self.groups.contains(&check.group))
+            .map(|check| CatalogDiagnostic {
+                severity: CatalogSeverity::Critical,
+                check_id: Some(check.id.clone()),
+                group: check.group.clone(),
+                message: format!("group '{}' is not an approved group", check.group),
+            });
+
+        let empty_groups = self
+            .groups
+            .iter()
+            .filter(|group| !checks.iter().any(|check| &check.group == *group))
+            .map(|group| CatalogDiagnostic {
+                severity: CatalogSeverity::Critical,
+                check_id: None,
+                group: group.clone(),
+                message: format!("group '{}' has no checks", group),
+            });
+
+        unapproved_groups.chain(empty_groups).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(id: &str, group: &str) -> Check {
+        named_check(id, "Corosync configuration file", group)
+    }
+
+    fn named_check(id: &str, name: &str, group: &str) -> Check {
+        serde_yaml::from_str(&format!(
+            r#"
+            id: {}
+            name: {}
+            group: {}
+            description: Corosync `token` timeout is set to expected value
+            remediation: Set the Corosync `token` timeout
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+            "#,
+            id, name, group
+        ))
+        .expect("the test check should be deserializable")
+    }
+
+    #[test]
+    fn validate_unique_ids() {
+        let checks = vec![
+            check("156F64", "Corosync"),
+            check("845CC9", "Corosync"),
+            check("156F64", "Pacemaker"),
+            check("156F64", "Pacemaker"),
+        ];
+
+        let diagnostics = UniqueIdValidator {}.validate(&checks);
+
+        assert_eq!(
+            diagnostics,
+            vec![CatalogDiagnostic {
+                severity: CatalogSeverity::Critical,
+                check_id: Some("156F64".to_string()),
+                group: "Pacemaker".to_string(),
+                message: "check id '156F64' is not unique".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn validate_unique_names() {
+        let checks = vec![
+            named_check("156F64", "Corosync token timeout", "Corosync"),
+            named_check("845CC9", "Pacemaker stonith", "Pacemaker"),
+            named_check("DA114A", "Corosync token timeout", "Corosync"),
+            named_check("845CC9", "Pacemaker stonith", "Pacemaker"),
+        ];
+
+        let diagnostics = UniqueNameValidator {}.validate(&checks);
+
+        assert_eq!(
+            diagnostics,
+            vec![CatalogDiagnostic {
+                severity: CatalogSeverity::Warning,
+                check_id: Some("156F64".to_string()),
+                group: "Corosync".to_string(),
+                message: "check name 'Corosync token timeout' is shared by checks 156F64, DA114A"
+                    .to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn validate_approved_groups() {
+        let checks = vec![check("156F64", "Corosync"), check("845CC9", "Corosinc")];
+        let groups = vec!["Corosync".to_string(), "Pacemaker".to_string()];
+
+        let diagnostics = ApprovedGroupValidator { groups: &groups }.validate(&checks);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                CatalogDiagnostic {
+                    severity: CatalogSeverity::Critical,
+                    check_id: Some("845CC9".to_string()),
+                    group: "Corosinc".to_string(),
+                    message: "group 'Corosinc' is not an approved group".to_string(),
+                },
+                CatalogDiagnostic {
+                    severity: CatalogSeverity::Critical,
+                    check_id: None,
+                    group: "Pacemaker".to_string(),
+                    message: "group 'Pacemaker' has no checks".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use super::schema_validator::collect_deprecations;
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+use jsonschema::JSONSchema;
+use serde_json;
+
+/// Reports only the deprecated properties used by a check, leaving the rest of the schema
+/// validation out. Meant for planning migrations ahead of schema upgrades.
+pub struct DeprecationValidator<'a> {
+    pub schema: &'a JSONSchema,
+}
+
+impl<'a> Validator for DeprecationValidator<'a> {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        collect_deprecations(json_check, check_id, self.schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::validation::get_json_schema;
+
+    #[test]
+    fn validate_deprecations_only() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            premium: true
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("the test string should be valid yaml");
+        let json_schema = get_json_schema();
+        let validator = DeprecationValidator {
+            schema: &json_schema,
+        };
+
+        let diagnostics = validator.validate(&json_value, "156F64");
+
+        assert!(diagnostics.len() == 1);
+        match &diagnostics[0] {
+            ValidationDiagnostic::Warning {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "Property 'premium' is deprecated and will be removed in the future"
+                );
+                assert_eq!(instance_path, "/premium");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+}
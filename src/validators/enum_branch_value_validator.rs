@@ -0,0 +1,232 @@
+use crate::dsl::engine::{collect_expression_references, collect_statement_references};
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+use rhai::{Engine, Expr, Stmt};
+use serde_json::json;
+
+// Statuses whose name in a value name hints at the branch the value is meant for, along with the
+// status of the branches it is not expected in.
+const STATUS_HINTS: [(&str, &str); 2] = [("warning", "passing"), ("passing", "warning")];
+
+pub struct EnumBranchValueValidator {}
+
+impl Validator for EnumBranchValueValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        validate_enum_branch_values(json_check, check_id)
+    }
+}
+
+struct Branch {
+    status: String,
+    values: Vec<String>,
+}
+
+fn get_status(statements: &[Stmt]) -> Option<String> {
+    match statements.last() {
+        Some(Stmt::Expr(expression)) => match &**expression {
+            Expr::StringConstant(status, _) => Some(status.to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Collects the branches of an if/else chain returning a status, along with the values referenced
+// by their condition and body.
+fn collect_branches(statements: &[Stmt], branches: &mut Vec<Branch>) {
+    match statements.last() {
+        Some(Stmt::If(flow, _)) => {
+            if let Some(status) = get_status(flow.body.statements()) {
+                let mut values = collect_expression_references(&flow.expr, "values");
+                values.extend(collect_statement_references(
+                    flow.body.statements(),
+                    "values",
+                ));
+                branches.push(Branch { status, values });
+            }
+            collect_branches(flow.branch.statements(), branches);
+        }
+        _ => {
+            if let Some(status) = get_status(statements) {
+                branches.push(Branch {
+                    status,
+                    values: collect_statement_references(statements, "values"),
+                });
+            }
+        }
+    }
+}
+
+fn validate_enum_branch_values(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    let engine = Engine::new_raw();
+
+    json_check
+        .get("expectations")
+        .unwrap_or(&json!([]))
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .enumerate()
+        .flat_map(|(index, expectation)| {
+            let mut branches = vec![];
+            if let Some(ast) = expectation
+                .get("expect_enum")
+                .and_then(|expect_enum| expect_enum.as_str())
+                .and_then(|expect_enum| engine.compile(expect_enum).ok())
+            {
+                collect_branches(ast.statements(), &mut branches);
+            }
+
+            let mut misplaced: Vec<(String, String, &str)> = branches
+                .into_iter()
+                .flat_map(|branch| {
+                    branch
+                        .values
+                        .into_iter()
+                        .filter_map(|value| {
+                            let (hint, _) = STATUS_HINTS.iter().find(|(hint, status)| {
+                                value.contains(hint) && branch.status == *status
+                            })?;
+                            Some((value, branch.status.clone(), *hint))
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            let mut reported = vec![];
+            misplaced.retain(|misplaced_value| {
+                let is_new = !reported.contains(misplaced_value);
+                reported.push(misplaced_value.clone());
+                is_new
+            });
+
+            misplaced
+                .into_iter()
+                .map(|(value, status, hint)| ValidationDiagnostic::Warning {
+                    check_id: check_id.to_string(),
+                    message: format!(
+                        "value '{}' hints at the \"{}\" status but is referenced in a branch returning \"{}\"",
+                        value, hint, status
+                    ),
+                    instance_path: format!("/expectations/{:?}", index),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn validate_misplaced_warning_value() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_passing_value
+                default: 30000
+              - name: expected_warning_value
+                default: 20000
+            expectations:
+              - name: timeout
+                expect_enum: |
+                  if facts.corosync_token_timeout == values.expected_warning_value {
+                    "passing"
+                  } else if facts.corosync_token_timeout == values.expected_passing_value {
+                    "warning"
+                  } else {
+                    "critical"
+                  }
+                failure_message: some critical message
+                warning_message: some warning message
+              - name: ordered_timeout
+                expect_enum: |
+                  if facts.corosync_token_timeout == values.expected_passing_value {
+                    "passing"
+                  } else if facts.corosync_token_timeout == values.expected_warning_value {
+                    "warning"
+                  } else {
+                    "critical"
+                  }
+                failure_message: some critical message
+                warning_message: some warning message
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = EnumBranchValueValidator {}.validate(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 2);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "value 'expected_warning_value' hints at the \"warning\" status but is referenced in a branch returning \"passing\""
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+        match &validation_errors[1] {
+            ValidationDiagnostic::Warning {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "value 'expected_passing_value' hints at the \"passing\" status but is referenced in a branch returning \"warning\""
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+
+    #[test]
+    fn validate_repeated_misplaced_value() {
+        let input = r#"
+            values:
+              - name: expected_passing_value
+                default: 30000
+              - name: expected_warning_value
+                default: 20000
+            expectations:
+              - name: timeout
+                expect_enum: |
+                  if facts.timeout > values.expected_warning_value && facts.timeout < values.expected_passing_value && facts.timeout != values.expected_warning_value {
+                    "passing"
+                  } else {
+                    "critical"
+                  }
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = EnumBranchValueValidator {}.validate(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::dsl::engine::collect_references;
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+use rhai::Engine;
+use serde_json::json;
+
+pub struct EnvKeyValidator<'a> {
+    pub allowed_env_keys: Option<&'a [String]>,
+}
+
+impl<'a> Validator for EnvKeyValidator<'a> {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        match self.allowed_env_keys {
+            Some(allowed_env_keys) => validate_env_keys(json_check, check_id, allowed_env_keys),
+            None => vec![],
+        }
+    }
+}
+
+// Number of single character edits turning a name into another one.
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+
+    for (left_index, left_char) in left.chars().enumerate() {
+        let mut current = vec![left_index + 1];
+        for (right_index, right_char) in right.iter().enumerate() {
+            let substitution = previous[right_index] + usize::from(left_char != *right_char);
+            current.push(
+                substitution
+                    .min(previous[right_index + 1] + 1)
+                    .min(current[right_index] + 1),
+            );
+        }
+        previous = current;
+    }
+
+    previous[right.len()]
+}
+
+fn suggest_env_key<'a>(env_key: &str, allowed_env_keys: &'a [String]) -> Option<&'a String> {
+    allowed_env_keys
+        .iter()
+        .map(|allowed_env_key| (edit_distance(env_key, allowed_env_key), allowed_env_key))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, allowed_env_key)| allowed_env_key)
+}
+
+fn validate_env_keys(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    allowed_env_keys: &[String],
+) -> Vec<ValidationDiagnostic> {
+    let engine = Engine::new_raw();
+
+    json_check
+        .get("values")
+        .unwrap_or(&json!([]))
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .enumerate()
+        .flat_map(|(value_index, value)| {
+            value
+                .get("conditions")
+                .and_then(|conditions| conditions.as_array())
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .flat_map(|(condition_index, condition)| {
+                    let mut env_keys = condition
+                        .get("when")
+                        .and_then(|when| when.as_str())
+                        .and_then(|when| engine.compile(when).ok())
+                        .map(|ast| collect_references(&ast, "env"))
+                        .unwrap_or_default();
+                    env_keys.sort();
+                    env_keys.dedup();
+
+                    env_keys
+                        .into_iter()
+                        .filter(|env_key| !allowed_env_keys.contains(env_key))
+                        .map(|env_key| {
+                            let message = match suggest_env_key(&env_key, allowed_env_keys) {
+                                Some(suggestion) => format!(
+                                    "env key '{}' is not a known env key, did you mean '{}'?",
+                                    env_key, suggestion
+                                ),
+                                None => format!("env key '{}' is not a known env key", env_key),
+                            };
+
+                            ValidationDiagnostic::Warning {
+                                check_id: check_id.to_string(),
+                                message,
+                                instance_path: format!(
+                                    "/values/{:?}/conditions/{:?}",
+                                    value_index, condition_index
+                                ),
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn validate_unknown_env_key() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_token_timeout
+                default: 5000
+                conditions:
+                  - value: 30000
+                    when: env.provider == "aws"
+                  - value: 20000
+                    when: env.providr == "azure" && env.cluster_type == "hana_scale_up"
+                  - value: 10000
+                    when: env.architecture == "ppc64le"
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+        "#;
+        let allowed_env_keys = vec!["provider".to_string(), "cluster_type".to_string()];
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = EnvKeyValidator {
+            allowed_env_keys: Some(&allowed_env_keys),
+        }
+        .validate(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 2);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "env key 'providr' is not a known env key, did you mean 'provider'?"
+                );
+                assert_eq!(instance_path, "/values/0/conditions/1");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+        match &validation_errors[1] {
+            ValidationDiagnostic::Warning {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(message, "env key 'architecture' is not a known env key");
+                assert_eq!(instance_path, "/values/0/conditions/2");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+
+        let validation_errors = EnvKeyValidator {
+            allowed_env_keys: None,
+        }
+        .validate(&json_value, "156F64");
+
+        assert!(validation_errors.is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+use regex::Regex;
+use serde_json::json;
+
+pub struct ExpectationNameValidator<'a> {
+    pub pattern: &'a Regex,
+}
+
+impl<'a> Validator for ExpectationNameValidator<'a> {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        validate_expectation_names(json_check, check_id, self.pattern)
+    }
+}
+
+fn validate_expectation_names(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    pattern: &Regex,
+) -> Vec<ValidationDiagnostic> {
+    json_check
+        .get("expectations")
+        .unwrap_or(&json!([]))
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .enumerate()
+        .filter_map(|(index, expectation)| {
+            let name = expectation.get("name")?.as_str()?;
+
+            match pattern.is_match(name) {
+                true => None,
+                false => Some(ValidationDiagnostic::Warning {
+                    check_id: check_id.to_string(),
+                    message: format!(
+                        "expectation name '{}' does not match the naming convention {}",
+                        name, pattern
+                    ),
+                    instance_path: format!("/expectations/{:?}/name", index),
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::types::DEFAULT_EXPECTATION_NAME_PATTERN;
+    use serde_json;
+
+    #[test]
+    fn validate_expectation_name_convention() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+              - name: My Timeout
+                expect: facts.corosync_token_timeout == 5000
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let pattern = Regex::new(DEFAULT_EXPECTATION_NAME_PATTERN).unwrap();
+        let validation_errors = validate_expectation_names(&json_value, "156F64", &pattern);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "expectation name 'My Timeout' does not match the naming convention ^[a-z][a-z0-9_]*$"
+                );
+                assert_eq!(instance_path, "/expectations/1/name");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+}
@@ -1,7 +1,12 @@
+use crate::dsl::engine::{collect_references, find_disallowed_functions};
 use crate::dsl::types::{ValidationDiagnostic, Validator};
-use rhai::{Engine, Expr, Stmt};
+use rhai::{ASTNode, Engine, Expr, Stmt, AST};
 use serde_json::json;
 
+pub const ENUM_RETURN_VALUES: [&str; 3] = ["passing", "warning", "critical"];
+
+const BOOLEAN_OPERATORS: [&str; 7] = ["==", "!=", "<", "<=", ">", ">=", "!"];
+
 pub struct ExpectationValidator<'a> {
     pub engine: &'a Engine,
 }
@@ -12,17 +17,92 @@ impl<'a> Validator for ExpectationValidator<'a> {
         json_check: &serde_json::Value,
         check_id: &str,
     ) -> Vec<ValidationDiagnostic> {
-        validate_expectations(json_check, check_id, self.engine)
+        let mut diagnostics: Vec<ValidationDiagnostic> =
+            validate_expectations_presence(json_check, check_id)
+                .into_iter()
+                .collect();
+        diagnostics.extend(validate_expectations(json_check, check_id, self.engine));
+        diagnostics.extend(validate_expect_same_interpolation(
+            json_check,
+            check_id,
+            self.engine,
+        ));
+        diagnostics
+    }
+}
+
+// A check without expectations can neither pass nor fail.
+fn validate_expectations_presence(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Option<ValidationDiagnostic> {
+    let has_expectations = json_check
+        .get("expectations")
+        .and_then(|expectations| expectations.as_array())
+        .is_some_and(|expectations| !expectations.is_empty());
+
+    match has_expectations {
+        true => None,
+        false => Some(ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: "check must have at least one expectation".to_string(),
+            instance_path: "/expectations".to_string(),
+        }),
+    }
+}
+
+// Only "${" opens an interpolation, a lone "$", as in "cost is $5", being plain text.
+fn has_interpolation(message: &str) -> bool {
+    message.contains("${")
+}
+
+// Whether an interpolation, opened with "${", lacks its closing brace. Rhai reports it as an
+// unterminated string at a position of the quoted message rather than of the YAML field.
+fn has_unterminated_interpolation(message: &str) -> bool {
+    let mut remaining = message;
+
+    while let Some(start) = remaining.find("${") {
+        let mut depth = 0;
+        let end = remaining[start + 2..].char_indices().find(|(_, c)| {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => return true,
+                '}' => depth -= 1,
+                _ => (),
+            }
+            false
+        });
+
+        match end {
+            Some((offset, _)) => remaining = &remaining[start + 2 + offset + 1..],
+            None => return true,
+        }
     }
+
+    false
 }
 
 fn validate_string_expression(
     expression: &str,
+    field: &str,
     engine: &Engine,
     check_id: &str,
     index: usize,
     allow_interpolated_strings: bool,
 ) -> Result<(), ValidationDiagnostic> {
+    if has_unterminated_interpolation(expression) {
+        return Err(ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: format!(
+                "unterminated interpolation in {}, close it with '}}'",
+                field
+            ),
+            instance_path: format!("/expectations/{:?}/{}", index, field),
+        });
+    }
+
+    let is_interpolation = has_interpolation(expression);
+
     match engine.compile(format!("`{}`", expression)) {
         Ok(ast) => {
             let statements = ast.statements();
@@ -38,10 +118,13 @@ fn validate_string_expression(
                 Stmt::Expr(expression) => match **expression {
                     Expr::StringConstant(_, _) => Ok(()),
                     Expr::InterpolatedString(_, _) => {
-                        if !allow_interpolated_strings {
+                        if !allow_interpolated_strings && is_interpolation {
                             Err(ValidationDiagnostic::Critical {
                                 check_id: check_id.to_string(),
-                                message: "String interpolation is not allowed here".to_string(),
+                                message: format!(
+                                    "String interpolation is not allowed here. Use a plain {} without ${{...}}",
+                                    field
+                                ),
                                 instance_path: format!("/expectations/{:?}", index).to_string(),
                             })
                         } else {
@@ -95,11 +178,415 @@ fn validate_expect_enum_content(
     results
 }
 
+fn collect_expression_return_values(expression: &Expr, return_values: &mut Vec<String>) {
+    match expression {
+        Expr::StringConstant(value, _) => return_values.push(value.to_string()),
+        Expr::Stmt(block) => collect_block_return_values(block.statements(), return_values),
+        _ => (),
+    }
+}
+
+fn collect_block_return_values(statements: &[Stmt], return_values: &mut Vec<String>) {
+    match statements.last() {
+        Some(Stmt::Expr(expression)) => collect_expression_return_values(expression, return_values),
+        Some(Stmt::Block(block)) => collect_block_return_values(block.statements(), return_values),
+        Some(Stmt::If(flow, _)) => {
+            collect_block_return_values(flow.body.statements(), return_values);
+            collect_block_return_values(flow.branch.statements(), return_values);
+        }
+        Some(Stmt::Switch(switch, _)) => switch
+            .1
+            .expressions
+            .iter()
+            .for_each(|case| collect_expression_return_values(&case.rhs, return_values)),
+        _ => (),
+    }
+}
+
+pub(crate) fn collect_enum_return_values(ast: &AST) -> Vec<String> {
+    let mut return_values = vec![];
+
+    collect_block_return_values(ast.statements(), &mut return_values);
+
+    ast.walk(&mut |nodes: &[ASTNode]| {
+        if let Some(ASTNode::Stmt(Stmt::Return(Some(expression), _, _))) = nodes.last() {
+            collect_expression_return_values(expression, &mut return_values);
+        }
+        true
+    });
+
+    return_values
+}
+
+fn lacks_final_else(statements: &[Stmt]) -> bool {
+    match statements.last() {
+        Some(Stmt::If(flow, _)) => {
+            flow.branch.is_empty() || lacks_final_else(flow.branch.statements())
+        }
+        Some(Stmt::Block(block)) => lacks_final_else(block.statements()),
+        _ => false,
+    }
+}
+
+// Whether the expression, or one of its branches, ends with a switch without a default case, so that
+// it returns unit for the unmatched values.
+fn lacks_switch_default(statements: &[Stmt]) -> bool {
+    match statements.last() {
+        Some(Stmt::Switch(switch, _)) => {
+            switch.1.def_case.is_none()
+                || switch.1.expressions.iter().any(|case| match &case.rhs {
+                    Expr::Stmt(block) => lacks_switch_default(block.statements()),
+                    _ => false,
+                })
+        }
+        Some(Stmt::If(flow, _)) => {
+            lacks_switch_default(flow.body.statements())
+                || lacks_switch_default(flow.branch.statements())
+        }
+        Some(Stmt::Block(block)) => lacks_switch_default(block.statements()),
+        _ => false,
+    }
+}
+
+// Whether the expression, or one of its branches, ends with a statement producing no value, so that
+// it returns unit instead of a status.
+fn ends_with_statement(statements: &[Stmt]) -> bool {
+    match statements.last() {
+        Some(
+            Stmt::Var(..) | Stmt::Assignment(..) | Stmt::While(..) | Stmt::Do(..) | Stmt::For(..),
+        ) => true,
+        Some(Stmt::If(flow, _)) => {
+            ends_with_statement(flow.body.statements())
+                || ends_with_statement(flow.branch.statements())
+        }
+        Some(Stmt::Block(block)) => ends_with_statement(block.statements()),
+        _ => false,
+    }
+}
+
+// Whether the expression is an if/else returning string literals, as an expect_enum would.
+fn returns_strings(statements: &[Stmt]) -> bool {
+    let mut return_values = vec![];
+
+    if let Some(Stmt::If(..)) = statements.last() {
+        collect_block_return_values(statements, &mut return_values);
+    }
+
+    !return_values.is_empty()
+}
+
+// Whether the expression ends with a comparison or a logical operation, as an expect would.
+fn is_boolean_expression(statements: &[Stmt]) -> bool {
+    match statements.last() {
+        Some(Stmt::FnCall(call, _)) => BOOLEAN_OPERATORS.contains(&call.name.as_str()),
+        Some(Stmt::Expr(expression)) => match &**expression {
+            Expr::FnCall(call, _) => BOOLEAN_OPERATORS.contains(&call.name.as_str()),
+            Expr::And(..) | Expr::Or(..) | Expr::BoolConstant(..) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn validate_expect_returning_strings(
+    ast: &AST,
+    check_id: &str,
+    index: usize,
+) -> Option<ValidationDiagnostic> {
+    match returns_strings(ast.statements()) {
+        true => Some(ValidationDiagnostic::Warning {
+            check_id: check_id.to_string(),
+            message: "expect returns strings instead of a boolean. Use expect_enum to return \"passing\", \"warning\" or \"critical\"".to_string(),
+            instance_path: format!("/expectations/{:?}", index).to_string(),
+        }),
+        false => None,
+    }
+}
+
+fn validate_boolean_expect_enum(
+    ast: &AST,
+    check_id: &str,
+    index: usize,
+) -> Option<ValidationDiagnostic> {
+    match is_boolean_expression(ast.statements()) {
+        true => Some(ValidationDiagnostic::Warning {
+            check_id: check_id.to_string(),
+            message: "expect_enum is a boolean expression returning no status. Use expect for a passing or critical result".to_string(),
+            instance_path: format!("/expectations/{:?}", index).to_string(),
+        }),
+        false => None,
+    }
+}
+
+fn validate_expect_enum_final_expression(
+    ast: &AST,
+    check_id: &str,
+    index: usize,
+) -> Option<ValidationDiagnostic> {
+    match ends_with_statement(ast.statements()) {
+        true => Some(ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: "expect_enum ends with a statement returning no status. End it with the returned status instead".to_string(),
+            instance_path: format!("/expectations/{:?}", index).to_string(),
+        }),
+        false => None,
+    }
+}
+
+fn validate_expect_enum_branches(
+    ast: &AST,
+    check_id: &str,
+    index: usize,
+) -> Option<ValidationDiagnostic> {
+    match lacks_final_else(ast.statements()) {
+        true => Some(ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: "not all branches return a status. Add a final else branch".to_string(),
+            instance_path: format!("/expectations/{:?}", index).to_string(),
+        }),
+        false => None,
+    }
+}
+
+fn validate_expect_enum_switch_default(
+    ast: &AST,
+    check_id: &str,
+    index: usize,
+) -> Option<ValidationDiagnostic> {
+    match lacks_switch_default(ast.statements()) {
+        true => Some(ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: "switch doesn't return a status for unmatched values. Add a default `_` case"
+                .to_string(),
+            instance_path: format!("/expectations/{:?}", index).to_string(),
+        }),
+        false => None,
+    }
+}
+
+fn validate_expect_enum_return_values(
+    ast: &AST,
+    check_id: &str,
+    index: usize,
+) -> Vec<Result<(), ValidationDiagnostic>> {
+    collect_enum_return_values(ast)
+        .into_iter()
+        .filter(|return_value| !ENUM_RETURN_VALUES.contains(&return_value.as_str()))
+        .map(|return_value| {
+            Err(ValidationDiagnostic::Critical {
+                check_id: check_id.to_string(),
+                message: format!(
+                    "unexpected return value \"{}\". Allowed return values are \"passing\", \"warning\" and \"critical\"",
+                    return_value
+                ),
+                instance_path: format!("/expectations/{:?}", index).to_string(),
+            })
+        })
+        .collect()
+}
+
+fn validate_warning_message_presence(
+    ast: &AST,
+    has_warning_message: bool,
+    check_id: &str,
+    index: usize,
+) -> Option<ValidationDiagnostic> {
+    let returns_warning = collect_enum_return_values(ast)
+        .iter()
+        .any(|return_value| return_value == "warning");
+
+    let message = match (returns_warning, has_warning_message) {
+        (true, false) => "expect_enum can return \"warning\" but no warning_message is given",
+        (false, true) => "warning_message is never used as expect_enum never returns \"warning\"",
+        _ => return None,
+    };
+
+    Some(ValidationDiagnostic::Warning {
+        check_id: check_id.to_string(),
+        message: message.to_string(),
+        instance_path: format!("/expectations/{:?}", index).to_string(),
+    })
+}
+
+// Messages which are not strings, e.g. null or a number, still deserialize into a Check.
+fn non_string_message(field: &str, check_id: &str, index: usize) -> ValidationDiagnostic {
+    ValidationDiagnostic::Critical {
+        check_id: check_id.to_string(),
+        message: format!("{} must be a string", field),
+        instance_path: format!("/expectations/{:?}/{}", index, field),
+    }
+}
+
+fn validate_message_fact_references(
+    message_expression: &str,
+    field: &str,
+    declared_facts: &[&str],
+    engine: &Engine,
+    check_id: &str,
+    index: usize,
+) -> Vec<Result<(), ValidationDiagnostic>> {
+    match engine.compile(format!("`{}`", message_expression)) {
+        Ok(ast) => {
+            let mut facts = collect_references(&ast, "facts");
+            facts.sort();
+            facts.dedup();
+
+            facts
+                .into_iter()
+                .filter(|fact| !declared_facts.contains(&fact.as_str()))
+                .map(|fact| {
+                    Err(ValidationDiagnostic::Critical {
+                        check_id: check_id.to_string(),
+                        message: format!("fact '{}' referenced in {} is not declared", fact, field),
+                        instance_path: format!("/expectations/{:?}", index).to_string(),
+                    })
+                })
+                .collect()
+        }
+        Err(_) => vec![],
+    }
+}
+
+// Every access to facts and values is checked, whichever branch of the expression it is in.
+fn validate_expression_references(
+    ast: &AST,
+    field: &str,
+    declared_facts: &[&str],
+    declared_values: &[&str],
+    check_id: &str,
+    index: usize,
+) -> Vec<Result<(), ValidationDiagnostic>> {
+    [
+        ("facts", "fact", declared_facts),
+        ("values", "value", declared_values),
+    ]
+    .into_iter()
+    .flat_map(|(variable, kind, declared)| {
+        let mut references = collect_references(ast, variable);
+        references.sort();
+        references.dedup();
+
+        references
+            .into_iter()
+            .filter(|reference| !declared.contains(&reference.as_str()))
+            .map(move |reference| {
+                Err(ValidationDiagnostic::Critical {
+                    check_id: check_id.to_string(),
+                    message: format!(
+                        "{} '{}' referenced in {} is not declared",
+                        kind, reference, field
+                    ),
+                    instance_path: format!("/expectations/{:?}", index).to_string(),
+                })
+            })
+    })
+    .collect()
+}
+
+// expect_same compares the result of the expression across the agents, which is the same
+// everywhere when no fact is referenced.
+fn validate_expect_same_fact_references(
+    ast: &AST,
+    check_id: &str,
+    index: usize,
+) -> Option<ValidationDiagnostic> {
+    match collect_references(ast, "facts").is_empty() {
+        true => Some(ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: "expect_same references no fact, so its result is the same on every agent"
+                .to_string(),
+            instance_path: format!("/expectations/{:?}", index).to_string(),
+        }),
+        false => None,
+    }
+}
+
+// Expectations are evaluated on the gathered facts, the environment being meant for the `when`
+// expressions of the check and of the value conditions.
+fn validate_env_references(
+    ast: &AST,
+    field: &str,
+    check_id: &str,
+    index: usize,
+) -> Option<ValidationDiagnostic> {
+    match collect_references(ast, "env").is_empty() {
+        true => None,
+        false => Some(ValidationDiagnostic::Warning {
+            check_id: check_id.to_string(),
+            message: format!(
+                "{} references env. Move the environment dependent logic into value conditions",
+                field
+            ),
+            instance_path: format!("/expectations/{:?}", index).to_string(),
+        }),
+    }
+}
+
+fn is_interpolated_string(expression: &str, engine: &Engine) -> bool {
+    if !has_interpolation(expression) {
+        return false;
+    }
+
+    match engine.compile(format!("`{}`", expression)) {
+        Ok(ast) => matches!(
+            ast.statements(),
+            [Stmt::Expr(expression)] if matches!(**expression, Expr::InterpolatedString(_, _))
+        ),
+        Err(_) => false,
+    }
+}
+
+// Interpolated failure messages are already rejected per expectation. When the whole check is
+// made of expect_same expectations, point the author at the way to get interpolation back.
+fn validate_expect_same_interpolation(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    engine: &Engine,
+) -> Option<ValidationDiagnostic> {
+    let expectations = json_check.get("expectations")?.as_array()?;
+
+    let is_expect_same_only = !expectations.is_empty()
+        && expectations
+            .iter()
+            .all(|expectation| expectation.get("expect_same").is_some());
+    let has_interpolated_messages = expectations.iter().any(|expectation| {
+        expectation
+            .get("failure_message")
+            .and_then(|message| message.as_str())
+            .is_some_and(|message| is_interpolated_string(message, engine))
+    });
+
+    match is_expect_same_only && has_interpolated_messages {
+        true => Some(ValidationDiagnostic::Warning {
+            check_id: check_id.to_string(),
+            message: "this check uses expect_same, so failure_message interpolation is unavailable. Use expect expectations if the messages need interpolation".to_string(),
+            instance_path: "/expectations".to_string(),
+        }),
+        false => None,
+    }
+}
+
+fn get_declared_names<'a>(json_check: &'a serde_json::Value, field: &str) -> Vec<&'a str> {
+    json_check
+        .get(field)
+        .and_then(|declarations| declarations.as_array())
+        .map(|declarations| {
+            declarations
+                .iter()
+                .filter_map(|declaration| declaration.get("name").and_then(|name| name.as_str()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn validate_expectations(
     json_check: &serde_json::Value,
     check_id: &str,
     engine: &Engine,
 ) -> Vec<ValidationDiagnostic> {
+    let declared_facts = get_declared_names(json_check, "facts");
+    let declared_values = get_declared_names(json_check, "values");
+
     let (_, expectation_expression_errors): (Vec<_>, Vec<_>) = json_check
         .get("expectations")
         .unwrap_or(&json!([]))
@@ -116,20 +603,62 @@ fn validate_expectations(
             let is_expect_same = expect_same.is_some();
             let is_expect_enum = expect_enum.is_some();
 
-            let expectation_expression = if is_expect {
-                expect.unwrap().as_str().unwrap()
+            // A non string expression, e.g. `expect_same: true`, is reported as the constant
+            // it is instead of being skipped.
+            let expectation_expression = match expect.or(expect_same).or(expect_enum) {
+                Some(serde_json::Value::String(expression)) => expression.to_string(),
+                Some(serde_json::Value::Null) | None => String::new(),
+                Some(expression) => expression.to_string(),
+            };
+
+            let expression_field = if is_expect {
+                "expect"
             } else if is_expect_same {
-                expect_same.unwrap().as_str().unwrap()
-            } else if is_expect_enum {
-                expect_enum.unwrap().as_str().unwrap()
+                "expect_same"
             } else {
-                ""
+                "expect_enum"
             };
 
+            if (is_expect || is_expect_same || is_expect_enum)
+                && expectation_expression.trim().is_empty()
+            {
+                return vec![Err(ValidationDiagnostic::Critical {
+                    check_id: check_id.to_string(),
+                    message: format!("{} expression is empty", expression_field),
+                    instance_path: format!("/expectations/{:?}", index).to_string(),
+                })];
+            }
+
             let mut results = vec![];
 
-            match engine.compile(expectation_expression) {
-                Ok(_) => results.push(Ok(())),
+            let compilation_result = engine.compile(&expectation_expression);
+
+            match &compilation_result {
+                Ok(ast) => {
+                    results.extend(find_disallowed_functions(ast).into_iter().map(|function| {
+                        Err(ValidationDiagnostic::Critical {
+                            check_id: check_id.to_string(),
+                            message: format!("function '{}' is not allowed", function),
+                            instance_path: format!("/expectations/{:?}", index).to_string(),
+                        })
+                    }));
+                    results.append(&mut validate_expression_references(
+                        ast,
+                        expression_field,
+                        &declared_facts,
+                        &declared_values,
+                        check_id,
+                        index,
+                    ));
+                    results.extend(
+                        validate_env_references(ast, expression_field, check_id, index).map(Err),
+                    );
+                    if is_expect_same {
+                        results.extend(
+                            validate_expect_same_fact_references(ast, check_id, index).map(Err),
+                        );
+                    }
+                }
                 Err(error) => results.push(Err(ValidationDiagnostic::Critical {
                     check_id: check_id.to_string(),
                     message: error.to_string(),
@@ -140,15 +669,29 @@ fn validate_expectations(
             let failure_message = value.get("failure_message");
             let warning_message = value.get("warning_message");
 
-            if failure_message.is_some() {
-                let failure_message_expression = failure_message.unwrap().as_str().unwrap();
-                results.push(validate_string_expression(
-                    failure_message_expression,
-                    engine,
-                    check_id,
-                    index,
-                    is_expect || is_expect_enum,
-                ));
+            match failure_message.map(|failure_message| failure_message.as_str()) {
+                Some(Some(failure_message_expression)) => {
+                    results.push(validate_string_expression(
+                        failure_message_expression,
+                        "failure_message",
+                        engine,
+                        check_id,
+                        index,
+                        is_expect || is_expect_enum,
+                    ));
+                    results.append(&mut validate_message_fact_references(
+                        failure_message_expression,
+                        "failure_message",
+                        &declared_facts,
+                        engine,
+                        check_id,
+                        index,
+                    ));
+                }
+                Some(None) => {
+                    results.push(Err(non_string_message("failure_message", check_id, index)))
+                }
+                None => (),
             }
 
             if warning_message.is_some() && !is_expect_enum {
@@ -158,38 +701,83 @@ fn validate_expectations(
                         .to_string(),
                     instance_path: format!("/expectations/{:?}", index).to_string(),
                 }));
-            } else if warning_message.is_some() {
-                let warning_message_expression = warning_message.unwrap().as_str().unwrap();
-                results.push(validate_string_expression(
-                    warning_message_expression,
-                    engine,
-                    check_id,
-                    index,
-                    is_expect_enum,
-                ));
+            } else if let Some(warning_message) = warning_message {
+                match warning_message.as_str() {
+                    Some(warning_message_expression) => {
+                        results.push(validate_string_expression(
+                            warning_message_expression,
+                            "warning_message",
+                            engine,
+                            check_id,
+                            index,
+                            is_expect_enum,
+                        ));
+                        results.append(&mut validate_message_fact_references(
+                            warning_message_expression,
+                            "warning_message",
+                            &declared_facts,
+                            engine,
+                            check_id,
+                            index,
+                        ));
+                    }
+                    None => {
+                        results.push(Err(non_string_message("warning_message", check_id, index)))
+                    }
+                }
+            }
+
+            if is_expect {
+                if let Ok(ast) = &compilation_result {
+                    results
+                        .extend(validate_expect_returning_strings(ast, check_id, index).map(Err));
+                }
             }
 
             if is_expect_enum {
                 results.append(&mut validate_expect_enum_content(
-                    expectation_expression,
+                    &expectation_expression,
                     check_id,
                     index,
                 ));
+
+                if let Ok(ast) = &compilation_result {
+                    results.extend(validate_boolean_expect_enum(ast, check_id, index).map(Err));
+                    results.append(&mut validate_expect_enum_return_values(
+                        ast, check_id, index,
+                    ));
+                    results.extend(validate_expect_enum_branches(ast, check_id, index).map(Err));
+                    results
+                        .extend(validate_expect_enum_switch_default(ast, check_id, index).map(Err));
+                    results.extend(
+                        validate_expect_enum_final_expression(ast, check_id, index).map(Err),
+                    );
+                    results.extend(
+                        validate_warning_message_presence(
+                            ast,
+                            warning_message.is_some(),
+                            check_id,
+                            index,
+                        )
+                        .map(Err),
+                    );
+                }
             }
 
             results
         })
         .partition(Result::is_ok);
 
-    return expectation_expression_errors
+    expectation_expression_errors
         .into_iter()
         .map(Result::unwrap_err)
-        .collect();
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dsl::engine::get_engine;
     use crate::dsl::types::Check;
     use rhai::Engine;
     use serde_json;
@@ -292,4 +880,867 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn validate_invalid_expect_enum_return_value() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_passing_value
+                default: 5000
+              - name: expected_warning_value
+                default: 3000
+            expectations:
+              - name: timeout
+                expect_enum: |
+                  if facts.corosync_token_timeout == values.expected_passing_value {
+                    "passed"
+                  } else if facts.corosync_token_timeout == values.expected_warning_value {
+                    "warning"
+                  } else {
+                    "critical"
+                  }
+                failure_message: some critical message
+                warning_message: some warning message
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 2);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(message, "passing return value not found");
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+        match &validation_errors[1] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "unexpected return value \"passed\". Allowed return values are \"passing\", \"warning\" and \"critical\""
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_expect_enum_missing_final_else() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect_enum: |
+                  if facts.corosync_token_timeout == 5000 {
+                    "passing"
+                  } else if facts.corosync_token_timeout == 3000 {
+                    "warning"
+                  } else if facts.corosync_token_timeout == 0 {
+                    "critical"
+                  }
+                failure_message: some critical message
+                warning_message: some warning message
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "not all branches return a status. Add a final else branch"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_expect_enum_switch_without_default() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect_enum: |
+                  switch facts.corosync_token_timeout {
+                    5000 => "passing",
+                    3000 => "warning",
+                    0 => "critical"
+                  }
+                failure_message: some critical message
+                warning_message: some warning message
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "switch doesn't return a status for unmatched values. Add a default `_` case"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_expect_enum_switch_return_value() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect_enum: |
+                  switch facts.corosync_token_timeout {
+                    5000 => "passing",
+                    3000 => "warning",
+                    _ => "failing"
+                  }
+                failure_message: some critical message
+                warning_message: some warning message
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical { message, .. } => {
+                assert_eq!(
+                    message,
+                    "unexpected return value \"failing\". Allowed return values are \"passing\", \"warning\" and \"critical\""
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn validate_expect_enum_warning_without_warning_message() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect_enum: |
+                  if facts.corosync_token_timeout == 5000 {
+                    "passing"
+                  } else if facts.corosync_token_timeout == 3000 {
+                    "warning"
+                  } else {
+                    "critical"
+                  }
+                failure_message: some critical message
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            c @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", c),
+            ValidationDiagnostic::Warning {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "expect_enum can return \"warning\" but no warning_message is given"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_undeclared_reference_in_expect_enum_branch() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_token_timeout
+                default: 5000
+              - name: tolerated_token_timeout
+                default: 3000
+            expectations:
+              - name: timeout
+                expect_enum: |
+                  if facts.corosync_token_timeout == values.expected_token_timeout {
+                    "passing"
+                  } else if facts.corosync_token_timeout == values.tolerated_token_timout {
+                    "warning"
+                  } else {
+                    "critical"
+                  }
+                failure_message: some critical message
+                warning_message: some warning message
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "value 'tolerated_token_timout' referenced in expect_enum is not declared"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+        }
+    }
+
+    #[test]
+    fn validate_mismatched_expectation_kinds() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: |
+                  if facts.corosync_token_timeout == 5000 {
+                    "passing"
+                  } else {
+                    "critical"
+                  }
+              - name: boolean_timeout
+                expect_enum: facts.corosync_token_timeout == 5000
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        let warnings: Vec<(&String, &String)> = validation_errors
+            .iter()
+            .filter_map(|diagnostic| match diagnostic {
+                ValidationDiagnostic::Warning {
+                    message,
+                    instance_path,
+                    ..
+                } => Some((message, instance_path)),
+                ValidationDiagnostic::Critical { .. } => None,
+            })
+            .collect();
+
+        assert_eq!(
+            warnings,
+            vec![
+                (
+                    &"expect returns strings instead of a boolean. Use expect_enum to return \"passing\", \"warning\" or \"critical\"".to_string(),
+                    &"/expectations/0".to_string()
+                ),
+                (
+                    &"expect_enum is a boolean expression returning no status. Use expect for a passing or critical result".to_string(),
+                    &"/expectations/1".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_expectation_referencing_env() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: x
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: env.provider == "aws" && facts.x == 1
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            c @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", c),
+            ValidationDiagnostic::Warning {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "expect references env. Move the environment dependent logic into value conditions"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_warning_message_without_warning_return_value() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_state
+                gatherer: corosync.conf
+            expectations:
+              - name: state
+                expect_enum: |
+                  if facts.corosync_token_state == "warning" {
+                    "critical"
+                  } else {
+                    "passing"
+                  }
+                failure_message: some critical message
+                warning_message: some warning message
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            c @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", c),
+            ValidationDiagnostic::Warning {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "warning_message is never used as expect_enum never returns \"warning\""
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_check_without_expectations() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations: []
+        "#;
+
+        let engine = Engine::new();
+        let validator = ExpectationValidator { engine: &engine };
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validator.validate(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(message, "check must have at least one expectation");
+                assert_eq!(instance_path, "/expectations");
+            }
+        }
+    }
+
+    #[test]
+    fn detect_unterminated_interpolation() {
+        assert!(has_unterminated_interpolation("timeout is ${facts.timeout"));
+        assert!(has_unterminated_interpolation("${facts.a} and ${facts.b"));
+        assert!(!has_unterminated_interpolation(
+            "timeout is ${facts.timeout}"
+        ));
+        assert!(!has_unterminated_interpolation("map is ${#{a: 1}.a} }"));
+        assert!(!has_unterminated_interpolation("cost is $5 {approx}"));
+    }
+
+    #[test]
+    fn validate_repeated_undeclared_reference() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timout > 0 && facts.corosync_token_timeout < facts.corosync_token_timout
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical { message, .. } => {
+                assert_eq!(
+                    message,
+                    "fact 'corosync_token_timout' referenced in expect is not declared"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn validate_constant_expect_same() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect_same: true
+              - name: same_timeout
+                expect_same: facts.corosync_token_timeout
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "expect_same references no fact, so its result is the same on every agent"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_expect_same_plain_message_with_dollar() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect_same: facts.corosync_token_timeout
+                failure_message: cost is $5 per node
+        "#;
+
+        let engine = Engine::new();
+        let validator = ExpectationValidator { engine: &engine };
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validator.validate(&json_value, "156F64");
+
+        assert!(validation_errors.is_empty());
+    }
+
+    #[test]
+    fn validate_expect_enum_ending_with_statement() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect_enum: |
+                  if facts.corosync_token_timeout == 5000 {
+                    "passing"
+                  } else {
+                    "warning"
+                  };
+                  let x = 1;
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "expect_enum ends with a statement returning no status. End it with the returned status instead"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_empty_expectation() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: ""
+              - name: same_timeout
+                expect_same: "   "
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 2);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(message, "expect expression is empty");
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+        match &validation_errors[1] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(message, "expect_same expression is empty");
+                assert_eq!(instance_path, "/expectations/1");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_disallowed_expectation_constructs() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            expectations:
+              - name: timeout
+                expect: |
+                  import "os" as os;
+                  facts.corosync_token_timeout == 5000
+              - name: timeout_printed
+                expect: |
+                  print(facts.corosync_token_timeout);
+                  facts.corosync_token_timeout == 5000
+        "#;
+
+        let engine = get_engine();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 2);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "'import' is a reserved keyword (line 1, position 1)"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+        match &validation_errors[1] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(message, "function 'print' is not allowed");
+                assert_eq!(instance_path, "/expectations/1");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_undeclared_fact_in_message() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_passing_value
+                default: 5000
+              - name: expected_warning_value
+                default: 3000
+            expectations:
+              - name: timeout
+                expect_enum: |
+                  if facts.corosync_token_timeout == values.expected_passing_value {
+                    "passing"
+                  } else if facts.corosync_token_timeout == values.expected_warning_value {
+                    "warning"
+                  } else {
+                    "critical"
+                  }
+                failure_message: Timeout value is ${facts.corosync_token_timeout}
+                warning_message: Timeout value is ${facts.corosync_timeout}, ${facts.corosync_token_timeout} being lower than ${facts.corosync_timeout}
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_expectations(&json_value, "156F64", &engine);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "fact 'corosync_timeout' referenced in warning_message is not declared"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        }
+    }
 }
@@ -0,0 +1,306 @@
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+use rhai::is_valid_identifier;
+
+pub struct FactValidator {}
+
+impl Validator for FactValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        let mut diagnostics = validate_fact_names(json_check, check_id);
+        diagnostics.extend(validate_gatherer_names(json_check, check_id));
+        diagnostics.extend(validate_duplicate_gatherings(json_check, check_id));
+        diagnostics
+    }
+}
+
+// Expectations reference facts as `facts.<name>`, which requires the name to be an identifier.
+fn validate_fact_names(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    json_check
+        .get("facts")
+        .and_then(|facts| facts.as_array())
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(index, fact)| {
+            let name = fact.get("name")?.as_str()?;
+
+            match is_valid_identifier(name) {
+                true => None,
+                false => Some(ValidationDiagnostic::Critical {
+                    check_id: check_id.to_string(),
+                    message: format!(
+                        "fact name '{}' is not a valid identifier and can't be referenced as facts.{}",
+                        name, name
+                    ),
+                    instance_path: format!("/facts/{:?}/name", index),
+                }),
+            }
+        })
+        .collect()
+}
+
+fn get_gatherer_whitespace_issue(gatherer: &str) -> Option<String> {
+    let trimmed = gatherer.trim();
+    let has_leading = gatherer.trim_start() != gatherer;
+    let has_trailing = gatherer.trim_end() != gatherer;
+
+    let issue = match (has_leading, has_trailing) {
+        (true, true) => "has leading and trailing whitespace",
+        (true, false) => "has leading whitespace",
+        (false, true) => "has trailing whitespace",
+        (false, false) if trimmed.contains(char::is_whitespace) => {
+            return Some(format!("gatherer '{}' contains whitespace", gatherer))
+        }
+        (false, false) => return None,
+    };
+
+    Some(format!(
+        "gatherer '{}' {}, use '{}'",
+        gatherer, issue, trimmed
+    ))
+}
+
+// Gatherer names with whitespace, usually copy-paste errors, never match a gatherer of wanda.
+fn validate_gatherer_names(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    json_check
+        .get("facts")
+        .and_then(|facts| facts.as_array())
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(index, fact)| {
+            let gatherer = fact.get("gatherer")?.as_str()?;
+
+            Some(ValidationDiagnostic::Critical {
+                check_id: check_id.to_string(),
+                message: get_gatherer_whitespace_issue(gatherer)?,
+                instance_path: format!("/facts/{:?}/gatherer", index),
+            })
+        })
+        .collect()
+}
+
+fn get_gathering(fact: &serde_json::Value) -> (Option<&str>, Option<&str>) {
+    (
+        fact.get("gatherer").and_then(|gatherer| gatherer.as_str()),
+        fact.get("argument").and_then(|argument| argument.as_str()),
+    )
+}
+
+// Facts gathered with the same gatherer and argument run the gatherer twice for the same result.
+fn validate_duplicate_gatherings(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    let facts: Vec<&serde_json::Value> = json_check
+        .get("facts")
+        .and_then(|facts| facts.as_array())
+        .into_iter()
+        .flatten()
+        .collect();
+
+    facts
+        .iter()
+        .enumerate()
+        .filter_map(|(index, fact)| {
+            let (gatherer, argument) = get_gathering(fact);
+            gatherer?;
+
+            let duplicated = facts[..index]
+                .iter()
+                .find(|previous| get_gathering(previous) == (gatherer, argument))?;
+            let duplicated_name = duplicated
+                .get("name")
+                .and_then(|name| name.as_str())
+                .unwrap_or_default();
+
+            Some(ValidationDiagnostic::Warning {
+                check_id: check_id.to_string(),
+                message: format!(
+                    "fact gathers the same gatherer and argument as fact '{}'",
+                    duplicated_name
+                ),
+                instance_path: format!("/facts/{:?}", index),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn validate_duplicate_fact_gatherings() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+              - name: corosync_consensus_timeout
+                gatherer: corosync.conf
+                argument: totem.consensus
+              - name: token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_duplicate_gatherings(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "fact gathers the same gatherer and argument as fact 'corosync_token_timeout'"
+                );
+                assert_eq!(instance_path, "/facts/2");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+
+    #[test]
+    fn validate_fact_names_are_identifiers() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+              - name: token-timeout
+                gatherer: corosync.conf
+                argument: totem.consensus
+              - name: 2fast
+                gatherer: corosync.conf
+                argument: totem.join
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_fact_names(&json_value, "156F64");
+
+        let diagnostics: Vec<(&str, &str)> = validation_errors
+            .iter()
+            .map(|diagnostic| match diagnostic {
+                ValidationDiagnostic::Critical {
+                    message,
+                    instance_path,
+                    ..
+                } => (message.as_str(), instance_path.as_str()),
+                w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            })
+            .collect();
+
+        assert_eq!(
+            diagnostics,
+            [
+                (
+                    "fact name 'token-timeout' is not a valid identifier and can't be referenced as facts.token-timeout",
+                    "/facts/1/name"
+                ),
+                (
+                    "fact name '2fast' is not a valid identifier and can't be referenced as facts.2fast",
+                    "/facts/2/name"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_gatherer_names_with_whitespace() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: "corosync.conf "
+                argument: totem.token
+              - name: corosync_consensus
+                gatherer: corosync conf
+                argument: totem.consensus
+              - name: corosync_join
+                gatherer: corosync.conf
+                argument: totem.join
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_gatherer_names(&json_value, "156F64");
+
+        let diagnostics: Vec<(&str, &str)> = validation_errors
+            .iter()
+            .map(|diagnostic| match diagnostic {
+                ValidationDiagnostic::Critical {
+                    message,
+                    instance_path,
+                    ..
+                } => (message.as_str(), instance_path.as_str()),
+                w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            })
+            .collect();
+
+        assert_eq!(
+            diagnostics,
+            [
+                (
+                    "gatherer 'corosync.conf ' has trailing whitespace, use 'corosync.conf'",
+                    "/facts/0/gatherer"
+                ),
+                (
+                    "gatherer 'corosync conf' contains whitespace",
+                    "/facts/1/gatherer"
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+
+pub struct GroupValidator<'a> {
+    pub allowed_groups: Option<&'a [String]>,
+}
+
+impl<'a> Validator for GroupValidator<'a> {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        json_check
+            .get("group")
+            .and_then(|group| group.as_str())
+            .and_then(|group| validate_group(group, check_id, self.allowed_groups))
+            .into_iter()
+            .collect()
+    }
+}
+
+fn validate_group(
+    group: &str,
+    check_id: &str,
+    allowed_groups: Option<&[String]>,
+) -> Option<ValidationDiagnostic> {
+    if group.trim().is_empty() {
+        return Some(ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: "group must not be empty".to_string(),
+            instance_path: "/group".to_string(),
+        });
+    }
+
+    let allowed_groups = allowed_groups?;
+    if allowed_groups
+        .iter()
+        .any(|allowed_group| allowed_group == group)
+    {
+        return None;
+    }
+
+    let suggestion = allowed_groups
+        .iter()
+        .find(|allowed_group| allowed_group.eq_ignore_ascii_case(group));
+    let message = match suggestion {
+        Some(suggestion) => format!(
+            "group '{}' is not an allowed group, did you mean '{}'?",
+            group, suggestion
+        ),
+        None => format!("group '{}' is not an allowed group", group),
+    };
+
+    Some(ValidationDiagnostic::Warning {
+        check_id: check_id.to_string(),
+        message,
+        instance_path: "/group".to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed_groups() -> Vec<String> {
+        vec!["Corosync".to_string(), "Pacemaker".to_string()]
+    }
+
+    fn assert_warning(diagnostic: Option<ValidationDiagnostic>, expected_message: &str) {
+        match diagnostic {
+            Some(ValidationDiagnostic::Warning {
+                message,
+                instance_path,
+                ..
+            }) => {
+                assert_eq!(message, expected_message);
+                assert_eq!(instance_path, "/group");
+            }
+            diagnostic => panic!("Unexpected diagnostic {:?}", diagnostic),
+        }
+    }
+
+    #[test]
+    fn validate_allowed_group() {
+        assert!(validate_group("Corosync", "156F64", Some(&allowed_groups())).is_none());
+    }
+
+    #[test]
+    fn validate_group_case_mismatch() {
+        assert_warning(
+            validate_group("corosync", "156F64", Some(&allowed_groups())),
+            "group 'corosync' is not an allowed group, did you mean 'Corosync'?",
+        );
+    }
+
+    #[test]
+    fn validate_unknown_group() {
+        assert_warning(
+            validate_group("SBD", "156F64", Some(&allowed_groups())),
+            "group 'SBD' is not an allowed group",
+        );
+    }
+
+    #[test]
+    fn validate_empty_group() {
+        assert!(validate_group("SBD", "156F64", None).is_none());
+
+        match validate_group("  ", "156F64", None) {
+            Some(ValidationDiagnostic::Critical { message, .. }) => {
+                assert_eq!(message, "group must not be empty")
+            }
+            diagnostic => panic!("Unexpected diagnostic {:?}", diagnostic),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::dsl::links::extract_links;
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+
+pub struct HttpsLinkValidator {}
+
+impl Validator for HttpsLinkValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        let links = extract_links(json_check);
+
+        links
+            .iter()
+            .filter_map(|link| {
+                let https_url = format!("https://{}", link.url.strip_prefix("http://")?);
+                let message = match links.iter().any(|other| other.url == https_url) {
+                    true => format!(
+                        "link '{}' is also given as '{}' in the check, use https consistently",
+                        link.url, https_url
+                    ),
+                    false => format!("link '{}' is insecure, use https if available", link.url),
+                };
+
+                Some(ValidationDiagnostic::Warning {
+                    check_id: check_id.to_string(),
+                    message,
+                    instance_path: link.source.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn validate_mixed_scheme_links() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value, see https://www.suse.com/docs.
+            remediation: |
+              ## Remediation
+              Follow http://www.suse.com/docs and http://example.com/corosync.
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = HttpsLinkValidator {}.validate(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 2);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "link 'http://www.suse.com/docs' is also given as 'https://www.suse.com/docs' in the check, use https consistently"
+                );
+                assert_eq!(instance_path, "/remediation");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+        match &validation_errors[1] {
+            ValidationDiagnostic::Warning { message, .. } => {
+                assert_eq!(
+                    message,
+                    "link 'http://example.com/corosync' is insecure, use https if available"
+                );
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+}
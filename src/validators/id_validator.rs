@@ -0,0 +1,61 @@
+use crate::dsl::id::canonical_id;
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+
+pub struct IdValidator {}
+
+impl Validator for IdValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        json_check
+            .get("id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| validate_id_case(id, check_id))
+            .into_iter()
+            .collect()
+    }
+}
+
+fn validate_id_case(id: &str, check_id: &str) -> Option<ValidationDiagnostic> {
+    let canonical_id = canonical_id(id);
+
+    match id == canonical_id {
+        true => None,
+        false => Some(ValidationDiagnostic::Warning {
+            check_id: check_id.to_string(),
+            message: format!(
+                "id '{}' is not uppercase, use '{}' or run tlint normalize-id",
+                id, canonical_id
+            ),
+            instance_path: "/id".to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_lowercase_id() {
+        assert!(validate_id_case("156F64", "156F64").is_none());
+
+        match validate_id_case("156f64", "156f64") {
+            Some(ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            }) => {
+                assert_eq!(check_id, "156f64");
+                assert_eq!(
+                    message,
+                    "id '156f64' is not uppercase, use '156F64' or run tlint normalize-id"
+                );
+                assert_eq!(instance_path, "/id");
+            }
+            diagnostic => panic!("Unexpected diagnostic {:?}", diagnostic),
+        }
+    }
+}
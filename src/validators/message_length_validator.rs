@@ -0,0 +1,106 @@
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+use serde_json::json;
+
+const MESSAGE_FIELDS: [&str; 2] = ["failure_message", "warning_message"];
+
+pub struct MessageLengthValidator {
+    pub max_length: usize,
+}
+
+impl Validator for MessageLengthValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        validate_message_lengths(json_check, check_id, self.max_length)
+    }
+}
+
+fn validate_message_lengths(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    max_length: usize,
+) -> Vec<ValidationDiagnostic> {
+    json_check
+        .get("expectations")
+        .unwrap_or(&json!([]))
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .enumerate()
+        .flat_map(|(index, expectation)| {
+            MESSAGE_FIELDS.iter().filter_map(move |field| {
+                let length = expectation.get(field)?.as_str()?.chars().count();
+
+                match length > max_length {
+                    true => Some(ValidationDiagnostic::Warning {
+                        check_id: check_id.to_string(),
+                        message: format!(
+                            "{} is {} characters long, longer messages than {} characters are truncated",
+                            field, length, max_length
+                        ),
+                        instance_path: format!("/expectations/{:?}", index),
+                    }),
+                    false => None,
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::types::DEFAULT_MAX_MESSAGE_LENGTH;
+    use serde_json;
+
+    #[test]
+    fn validate_long_messages() {
+        let input = format!(
+            r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+                failure_message: Timeout is not set as expected
+              - name: long_timeout
+                expect: facts.corosync_token_timeout == 5000
+                failure_message: {}
+            "#,
+            "a".repeat(300)
+        );
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(&input).expect("Unable to parse yaml");
+        let validation_errors =
+            validate_message_lengths(&json_value, "156F64", DEFAULT_MAX_MESSAGE_LENGTH);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "failure_message is 300 characters long, longer messages than 255 characters are truncated"
+                );
+                assert_eq!(instance_path, "/expectations/1");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+}
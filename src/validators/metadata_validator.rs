@@ -0,0 +1,290 @@
+use crate::dsl::types::{ValidationDiagnostic, Validator, CHECK_FIELDS};
+use rhai::{Engine, Expr, FnCallExpr, Stmt};
+
+pub struct MetadataValidator {}
+
+impl Validator for MetadataValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        validate_metadata(json_check, check_id)
+    }
+}
+
+fn validate_provider(provider: &serde_json::Value, check_id: &str) -> Option<ValidationDiagnostic> {
+    let is_list_of_strings = match provider.as_array() {
+        Some(providers) => providers.iter().all(serde_json::Value::is_string),
+        None => false,
+    };
+
+    match is_list_of_strings {
+        true => None,
+        false => Some(ValidationDiagnostic::Critical {
+            check_id: check_id.to_string(),
+            message: "provider must be a list of strings".to_string(),
+            instance_path: "/metadata/provider".to_string(),
+        }),
+    }
+}
+
+fn is_env_provider(expression: &Expr) -> bool {
+    match expression {
+        Expr::Dot(binary, _, _) => {
+            matches!(&binary.lhs, Expr::Variable(name, _, _) if name.1 == "env")
+                && matches!(&binary.rhs, Expr::Property(property, _) if property.2 == "provider")
+        }
+        _ => false,
+    }
+}
+
+// Only `env.provider == "x"` comparisons, possibly joined with `||`, are understood. Any other
+// expression doesn't constrain the providers as far as this heuristic is concerned.
+fn get_when_providers(expression: &Expr) -> Option<Vec<String>> {
+    match expression {
+        Expr::Or(binary, _) => {
+            let mut providers = get_when_providers(&binary.lhs)?;
+            providers.extend(get_when_providers(&binary.rhs)?);
+            Some(providers)
+        }
+        Expr::FnCall(call, _) => get_compared_provider(call).map(|provider| vec![provider]),
+        _ => None,
+    }
+}
+
+fn get_compared_provider(call: &FnCallExpr) -> Option<String> {
+    if call.name != "==" {
+        return None;
+    }
+
+    match call.args.as_slice() {
+        [lhs, Expr::StringConstant(provider, _)] if is_env_provider(lhs) => {
+            Some(provider.to_string())
+        }
+        [Expr::StringConstant(provider, _), rhs] if is_env_provider(rhs) => {
+            Some(provider.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn validate_when_providers(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Option<ValidationDiagnostic> {
+    let when = json_check.get("when")?.as_str()?;
+    let metadata_providers: Vec<&str> = json_check
+        .get("metadata")?
+        .get("provider")?
+        .as_array()?
+        .iter()
+        .filter_map(|provider| provider.as_str())
+        .collect();
+
+    let ast = Engine::new_raw().compile_expression(when).ok()?;
+    let when_providers = match ast.statements() {
+        [Stmt::Expr(expression)] => get_when_providers(expression)?,
+        [Stmt::FnCall(call, _)] => vec![get_compared_provider(call)?],
+        _ => return None,
+    };
+
+    let is_disjoint = !when_providers
+        .iter()
+        .any(|provider| metadata_providers.contains(&provider.as_str()));
+
+    match is_disjoint {
+        true => Some(ValidationDiagnostic::Warning {
+            check_id: check_id.to_string(),
+            message: format!(
+                "when only matches providers {} while metadata.provider lists {}",
+                when_providers.join(", "),
+                metadata_providers.join(", ")
+            ),
+            instance_path: "/when".to_string(),
+        }),
+        false => None,
+    }
+}
+
+// Metadata is free form, so a key named as a check field is only reported as a warning.
+fn validate_shadowed_fields(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    json_check
+        .get("metadata")
+        .and_then(|metadata| metadata.as_object())
+        .into_iter()
+        .flat_map(|metadata| metadata.keys())
+        .filter(|key| CHECK_FIELDS.contains(&key.as_str()))
+        .map(|key| ValidationDiagnostic::Warning {
+            check_id: check_id.to_string(),
+            message: format!(
+                "metadata key '{}' shadows the check field of the same name, move it to the top level if it is meant to be the check {}",
+                key, key
+            ),
+            instance_path: format!("/metadata/{}", key),
+        })
+        .collect()
+}
+
+fn validate_metadata(json_check: &serde_json::Value, check_id: &str) -> Vec<ValidationDiagnostic> {
+    json_check
+        .get("metadata")
+        .and_then(|metadata| metadata.get("provider"))
+        .and_then(|provider| validate_provider(provider, check_id))
+        .into_iter()
+        .chain(validate_when_providers(json_check, check_id))
+        .chain(validate_shadowed_fields(json_check, check_id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    fn check_with_metadata(metadata: &str) -> serde_json::Value {
+        check_with_when_and_metadata("true", metadata)
+    }
+
+    fn check_with_when_and_metadata(when: &str, metadata: &str) -> serde_json::Value {
+        let input = format!(
+            r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            when: '{}'
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            metadata:
+              {}
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+        "#,
+            when, metadata
+        );
+
+        serde_yaml::from_str(&input).expect("Unable to parse yaml")
+    }
+
+    #[test]
+    fn validate_ok_provider() {
+        let json_value = check_with_metadata("provider: [aws, azure]");
+
+        assert!(validate_metadata(&json_value, "156F64").is_empty());
+    }
+
+    #[test]
+    fn validate_scalar_provider() {
+        let json_value = check_with_metadata("provider: aws");
+        let validation_errors = validate_metadata(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(message, "provider must be a list of strings");
+                assert_eq!(instance_path, "/metadata/provider");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_mixed_provider() {
+        let json_value = check_with_metadata("provider: [aws, 42]");
+        let validation_errors = validate_metadata(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(message, "provider must be a list of strings");
+                assert_eq!(instance_path, "/metadata/provider");
+            }
+        }
+    }
+
+    #[test]
+    fn validate_when_matching_metadata_providers() {
+        let json_value = check_with_when_and_metadata(
+            r#"env.provider == "gcp" || env.provider == "aws""#,
+            "provider: [aws, azure]",
+        );
+
+        assert!(validate_metadata(&json_value, "156F64").is_empty());
+    }
+
+    #[test]
+    fn validate_when_disjoint_from_metadata_providers() {
+        let json_value =
+            check_with_when_and_metadata(r#"env.provider == "aws""#, "provider: [azure]");
+        let validation_errors = validate_metadata(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "when only matches providers aws while metadata.provider lists azure"
+                );
+                assert_eq!(instance_path, "/when");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+
+        let json_value = check_with_when_and_metadata(
+            r#"env.provider == "gcp" || env.provider == "aws""#,
+            "provider: [azure]",
+        );
+
+        assert!(validate_metadata(&json_value, "156F64").len() == 1);
+    }
+
+    #[test]
+    fn validate_metadata_shadowing_check_field() {
+        let json_value = check_with_metadata("name: foo");
+        let validation_errors = validate_metadata(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            c @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", c),
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "metadata key 'name' shadows the check field of the same name, move it to the top level if it is meant to be the check name"
+                );
+                assert_eq!(instance_path, "/metadata/name");
+            }
+        }
+    }
+}
@@ -1,3 +1,21 @@
+pub mod catalog_validator;
+pub mod deprecation_validator;
+pub mod enum_branch_value_validator;
+pub mod env_key_validator;
+pub mod expectation_name_validator;
 pub mod expectation_validator;
+pub mod fact_validator;
+pub mod group_validator;
+pub mod https_link_validator;
+pub mod id_validator;
+pub mod message_length_validator;
+pub mod metadata_validator;
+pub mod placeholder_validator;
+pub mod quoted_number_validator;
+pub mod roundtrip_validator;
 pub mod schema_validator;
+pub mod strict_schema_validator;
+pub mod text_interpolation_validator;
+pub mod value_range_validator;
 pub mod value_validator;
+pub mod warning_remediation_validator;
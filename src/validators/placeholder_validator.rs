@@ -0,0 +1,136 @@
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+
+const TEXT_FIELDS: [&str; 2] = ["description", "remediation"];
+
+pub struct PlaceholderValidator {}
+
+impl Validator for PlaceholderValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        validate_placeholders(json_check, check_id)
+    }
+}
+
+fn find_placeholders(text: &str) -> Vec<&str> {
+    let mut placeholders = vec![];
+    let mut remaining = text;
+
+    while let Some(start) = remaining.find("{{") {
+        match remaining[start..].find("}}") {
+            Some(length) => {
+                let end = start + length + 2;
+                placeholders.push(&remaining[start..end]);
+                remaining = &remaining[end..];
+            }
+            None => break,
+        }
+    }
+
+    placeholders
+}
+
+fn validate_placeholders(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    TEXT_FIELDS
+        .iter()
+        .flat_map(|field| {
+            let text = json_check
+                .get(field)
+                .and_then(|value| value.as_str())
+                .unwrap_or_default();
+
+            find_placeholders(text)
+                .into_iter()
+                .map(|placeholder| ValidationDiagnostic::Warning {
+                    check_id: check_id.to_string(),
+                    message: format!("template placeholder `{}` appears unrendered", placeholder),
+                    instance_path: format!("/{}", field),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn validate_ok_check() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_placeholders(&json_value, "156F64");
+
+        assert!(validation_errors.is_empty());
+    }
+
+    #[test]
+    fn validate_unrendered_placeholder() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              Set the timeout to {{ platform.corosync.expectedTokenTimeout }}
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_placeholders(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "template placeholder `{{ platform.corosync.expectedTokenTimeout }}` appears unrendered"
+                );
+                assert_eq!(instance_path, "/remediation");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+}
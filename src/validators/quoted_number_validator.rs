@@ -0,0 +1,151 @@
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+use serde_json::json;
+
+pub struct QuotedNumberValidator {}
+
+impl Validator for QuotedNumberValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        validate_quoted_numbers(json_check, check_id)
+    }
+}
+
+fn is_numeric_string(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+
+    digits.chars().any(|character| character.is_ascii_digit())
+        && digits
+            .chars()
+            .all(|character| character.is_ascii_digit() || character == '.')
+        && text.parse::<f64>().is_ok()
+}
+
+fn validate_quoted_number(
+    value: Option<&serde_json::Value>,
+    field: &str,
+    check_id: &str,
+    instance_path: String,
+) -> Option<ValidationDiagnostic> {
+    let text = value?.as_str()?;
+
+    match is_numeric_string(text) {
+        true => Some(ValidationDiagnostic::Warning {
+            check_id: check_id.to_string(),
+            message: format!(
+                "{} \"{}\" is a string compared as such. Remove the quotes if it is meant to be a number",
+                field, text
+            ),
+            instance_path,
+        }),
+        false => None,
+    }
+}
+
+fn validate_quoted_numbers(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    json_check
+        .get("values")
+        .unwrap_or(&json!([]))
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .enumerate()
+        .flat_map(|(value_index, value)| {
+            let default_diagnostic = validate_quoted_number(
+                value.get("default"),
+                "default",
+                check_id,
+                format!("/values/{:?}", value_index),
+            );
+
+            let condition_diagnostics = value
+                .get("conditions")
+                .and_then(|conditions| conditions.as_array())
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .filter_map(move |(condition_index, condition)| {
+                    validate_quoted_number(
+                        condition.get("value"),
+                        "value",
+                        check_id,
+                        format!("/values/{:?}/conditions/{:?}", value_index, condition_index),
+                    )
+                });
+
+            default_diagnostic.into_iter().chain(condition_diagnostics)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn validate_quoted_number_default() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_token_timeout
+                default: "5000"
+                conditions:
+                  - value: 30000
+                    when: env.provider == "aws"
+              - name: expected_provider
+                default: azure
+              - name: expected_version
+                default: "1.2.3"
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = QuotedNumberValidator {}.validate(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "default \"5000\" is a string compared as such. Remove the quotes if it is meant to be a number"
+                );
+                assert_eq!(instance_path, "/values/0");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+
+    #[test]
+    fn detect_numeric_strings() {
+        assert!(is_numeric_string("5000"));
+        assert!(is_numeric_string("-0.5"));
+        assert!(!is_numeric_string("1.2.3"));
+        assert!(!is_numeric_string("inf"));
+        assert!(!is_numeric_string("-"));
+        assert!(!is_numeric_string(""));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::dsl::types::{Check, ValidationDiagnostic, Validator};
+
+// Reports the properties `Check` drops, as deserializing and serializing a check again, e.g. to
+// rewrite it, would lose them.
+pub struct RoundtripValidator {}
+
+impl Validator for RoundtripValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        // Checks which can't be deserialized are already reported by the schema rule
+        let Ok(check) = serde_json::from_value::<Check>(json_check.clone()) else {
+            return vec![];
+        };
+        let roundtrip = serde_json::to_value(check).expect("a check should be serializable");
+
+        let mut lost_paths = vec![];
+        collect_lost_paths(json_check, &roundtrip, "", &mut lost_paths);
+
+        lost_paths
+            .into_iter()
+            .map(|path| ValidationDiagnostic::Warning {
+                check_id: check_id.to_string(),
+                message: "property is lost when the check is deserialized and serialized again"
+                    .to_string(),
+                instance_path: path,
+            })
+            .collect()
+    }
+}
+
+fn collect_lost_paths(
+    original: &serde_json::Value,
+    roundtrip: &serde_json::Value,
+    path: &str,
+    lost_paths: &mut Vec<String>,
+) {
+    match (original, roundtrip) {
+        (serde_json::Value::Object(original), serde_json::Value::Object(roundtrip)) => {
+            for (key, value) in original {
+                let key_path = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+
+                match roundtrip.get(key) {
+                    Some(serde_json::Value::Null) if !value.is_null() => lost_paths.push(key_path),
+                    Some(roundtrip_value) => {
+                        collect_lost_paths(value, roundtrip_value, &key_path, lost_paths)
+                    }
+                    None => lost_paths.push(key_path),
+                }
+            }
+        }
+        (serde_json::Value::Array(original), serde_json::Value::Array(roundtrip)) => {
+            for (index, value) in original.iter().enumerate() {
+                let index_path = format!("{}/{}", path, index);
+
+                match roundtrip.get(index) {
+                    Some(roundtrip_value) => {
+                        collect_lost_paths(value, roundtrip_value, &index_path, lost_paths)
+                    }
+                    None => lost_paths.push(index_path),
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn validate_roundtrip() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            severity: warning
+            premium: false
+            metadata:
+              target_type: cluster
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+                source: corosync
+            values:
+              - name: expected_token_timeout
+                default: 5000
+                conditions:
+                  - value: 30000
+                    when: env.provider == "azure"
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+                failure_message: Timeout is not set as expected
+            labels: [corosync]
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = RoundtripValidator {}.validate(&json_value, "156F64");
+
+        let paths: Vec<&str> = validation_errors
+            .iter()
+            .map(|diagnostic| match diagnostic {
+                ValidationDiagnostic::Warning { instance_path, .. } => instance_path.as_str(),
+                c @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", c),
+            })
+            .collect();
+
+        assert_eq!(paths, ["/facts/0/source", "/labels"]);
+    }
+}
@@ -1,26 +1,34 @@
 use crate::dsl::types::{ValidationDiagnostic, Validator};
-use jsonschema::{output::BasicOutput, JSONSchema};
+use jsonschema::{
+    error::ValidationErrorKind,
+    output::{BasicOutput, ErrorDescription, OutputUnit},
+    JSONSchema, ValidationError,
+};
 use serde_json;
+use std::collections::BTreeMap;
+
+const REQUIRED_PROPERTY_SUFFIX: &str = " is a required property";
 
 pub struct SchemaValidator<'a> {
     pub schema: &'a JSONSchema,
+    /// Report the structured output of the jsonschema crate instead of summarized messages.
+    pub raw_errors: bool,
+    /// Report the deprecated properties used by the check as warnings.
+    pub deprecations: bool,
 }
 
-fn collect_deprecations(
+pub(crate) fn collect_deprecations(
     json_check: &serde_json::Value,
     check_id: &str,
     schema: &JSONSchema,
 ) -> Vec<ValidationDiagnostic> {
     match schema.apply(json_check).basic() {
-        // FIXME: crate jsonschema does not resolve "$ref" to type definitions and therefore can
-        // not detect deprecations in linked types
+        // Deprecations in linked types are found since the bundled schemas are compiled with
+        // their "$ref"s inlined
         BasicOutput::Valid(annotations) => annotations
             .into_iter()
             .filter(|annotation| match annotation.value().get("deprecated") {
-                Some(val) => match val.as_bool() {
-                    Some(is_deprecated) => is_deprecated,
-                    None => false,
-                },
+                Some(val) => val.as_bool().unwrap_or_default(),
                 None => false,
             })
             .map(|annotation| {
@@ -51,7 +59,77 @@ impl<'a> Validator for SchemaValidator<'a> {
         json_check: &serde_json::Value,
         check_id: &str,
     ) -> Vec<ValidationDiagnostic> {
-        validate_schema(json_check, check_id, self.schema)
+        let mut diagnostics = match self.raw_errors {
+            true => validate_schema_raw(json_check, check_id, self.schema),
+            false => validate_schema(json_check, check_id, self.schema),
+        };
+        if self.deprecations {
+            diagnostics.extend(collect_deprecations(json_check, check_id, self.schema));
+        }
+
+        diagnostics
+    }
+}
+
+fn format_alternatives(alternatives: &[&str]) -> String {
+    match alternatives.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+// The 'oneOf' error message only states that none of the sub-schemas matched. Instead, report
+// what is missing when the sub-schemas differ by a single required property, or the errors of
+// the sub-schema which came closest to matching otherwise.
+fn summarize_one_of_error(
+    error: &ValidationError,
+    schema_errors: &[OutputUnit<ErrorDescription>],
+) -> String {
+    let schema_path = format!("{}/", error.schema_path);
+    let instance_path = error.instance_path.to_string();
+
+    let mut branches_errors: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    schema_errors
+        .iter()
+        .filter(|schema_error| {
+            let location = schema_error.instance_location().to_string();
+            location == instance_path || location.starts_with(&format!("{}/", instance_path))
+        })
+        .for_each(|schema_error| {
+            let keyword_location = schema_error.keyword_location().to_string();
+            let branch = keyword_location
+                .strip_prefix(&schema_path)
+                .and_then(|branch_location| branch_location.split('/').next())
+                .and_then(|branch| branch.parse::<usize>().ok());
+
+            if let Some(branch) = branch {
+                branches_errors
+                    .entry(branch)
+                    .or_default()
+                    .push(schema_error.error_description().to_string());
+            }
+        });
+
+    let missing_properties: Vec<&str> = branches_errors
+        .values()
+        .filter_map(|errors| match errors.as_slice() {
+            [error] => error.strip_suffix(REQUIRED_PROPERTY_SUFFIX),
+            _ => None,
+        })
+        .collect();
+
+    if !branches_errors.is_empty() && missing_properties.len() == branches_errors.len() {
+        return format!(
+            "one of {}{}",
+            format_alternatives(&missing_properties),
+            REQUIRED_PROPERTY_SUFFIX
+        );
+    }
+
+    match branches_errors.values().min_by_key(|errors| errors.len()) {
+        Some(errors) => errors.join("; "),
+        None => error.to_string(),
     }
 }
 
@@ -60,21 +138,49 @@ fn validate_schema(
     check_id: &str,
     schema: &JSONSchema,
 ) -> Vec<ValidationDiagnostic> {
-    let deprecation_warnings = collect_deprecations(json_check, check_id, schema);
-
-    let mut validation_errors = match schema.validate(json_check) {
+    match schema.validate(json_check) {
         Ok(_) => vec![],
-        Err(errors) => errors
-            .map(|error| ValidationDiagnostic::Critical {
-                check_id: check_id.to_string(),
-                message: error.to_string(),
-                instance_path: error.instance_path.to_string(),
-            })
-            .collect(),
-    };
+        Err(errors) => {
+            let schema_errors = match schema.apply(json_check).basic() {
+                BasicOutput::Invalid(schema_errors) => schema_errors.into_iter().collect(),
+                BasicOutput::Valid(_) => vec![],
+            };
+
+            errors
+                .map(|error| {
+                    let message = match error.kind {
+                        ValidationErrorKind::OneOfNotValid => {
+                            summarize_one_of_error(&error, &schema_errors)
+                        }
+                        _ => error.to_string(),
+                    };
+
+                    ValidationDiagnostic::Critical {
+                        check_id: check_id.to_string(),
+                        message,
+                        instance_path: error.instance_path.to_string(),
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+fn validate_schema_raw(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    schema: &JSONSchema,
+) -> Vec<ValidationDiagnostic> {
+    let output = schema.apply(json_check).basic();
+    if output.is_valid() {
+        return vec![];
+    }
 
-    validation_errors.extend(deprecation_warnings);
-    validation_errors
+    vec![ValidationDiagnostic::Critical {
+        check_id: check_id.to_string(),
+        message: serde_json::to_string(&output).expect("a schema output should be serializable"),
+        instance_path: String::new(),
+    }]
 }
 
 #[cfg(test)]
@@ -119,6 +225,8 @@ mod tests {
         let json_schema = get_json_schema();
         let validator = SchemaValidator {
             schema: &json_schema,
+            raw_errors: false,
+            deprecations: true,
         };
 
         let expected_check_id = "156F64";
@@ -197,6 +305,8 @@ mod tests {
         let json_schema = get_json_schema();
         let validator = SchemaValidator {
             schema: &json_schema,
+            raw_errors: false,
+            deprecations: true,
         };
 
         let expected_check_id = "156F64";
@@ -266,4 +376,98 @@ mod tests {
         assert!(validation_result.is_empty());
         assert!(deserialization_result.is_ok());
     }
+
+    #[test]
+    fn validate_ambiguous_expectation() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+                expect_same: facts.corosync_token_timeout
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("the test string should be valid yaml");
+        let json_schema = get_json_schema();
+        let diagnostics = validate_schema(&json_value, "156F64", &json_schema);
+
+        assert!(diagnostics.len() == 1);
+
+        match &diagnostics[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "Additional properties are not allowed ('expect_same' was unexpected)"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+        };
+    }
+
+    #[test]
+    fn validate_raw_schema_errors() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == 5000
+                expect_same: facts.corosync_token_timeout
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("the test string should be valid yaml");
+        let json_schema = get_json_schema();
+        let validator = SchemaValidator {
+            schema: &json_schema,
+            raw_errors: true,
+            deprecations: true,
+        };
+
+        let diagnostics = validator.validate(&json_value, "156F64");
+
+        assert!(diagnostics.len() == 1);
+        match &diagnostics[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical { message, .. } => {
+                let output: serde_json::Value =
+                    serde_json::from_str(message).expect("the raw output should be JSON");
+
+                assert_eq!(output["valid"], false);
+                assert!(output["errors"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .any(|error| error["instanceLocation"] == "/expectations/0"));
+            }
+        };
+    }
 }
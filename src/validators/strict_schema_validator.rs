@@ -0,0 +1,101 @@
+use crate::dsl::types::{ValidationDiagnostic, Validator, CHECK_FIELDS};
+
+pub struct StrictSchemaValidator {}
+
+impl Validator for StrictSchemaValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        validate_known_fields(json_check, check_id)
+    }
+}
+
+fn validate_known_fields(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    match json_check.as_object() {
+        Some(properties) => properties
+            .keys()
+            .filter(|property| !CHECK_FIELDS.contains(&property.as_str()))
+            .map(|property| ValidationDiagnostic::Warning {
+                check_id: check_id.to_string(),
+                message: format!("Property '{}' is not a known check property", property),
+                instance_path: format!("/{}", property),
+            })
+            .collect(),
+        None => vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::validation::SCHEMAS;
+    use serde_json;
+
+    #[test]
+    fn validate_unknown_fields() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            severity: critical
+            category: cluster
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            values:
+              - name: expected_token_timeout
+                default: 5000
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_known_fields(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(message, "Property 'category' is not a known check property");
+                assert_eq!(instance_path, "/category");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+
+    #[test]
+    fn check_fields_match_schema_properties() {
+        let schema: serde_json::Value =
+            serde_json::from_str(SCHEMAS[SCHEMAS.len() - 1].1).expect("Unable to parse schema");
+        let mut properties: Vec<&str> = schema["properties"]
+            .as_object()
+            .expect("the schema should declare properties")
+            .keys()
+            .map(String::as_str)
+            .collect();
+        let mut check_fields = CHECK_FIELDS.to_vec();
+        properties.sort();
+        check_fields.sort();
+
+        assert_eq!(check_fields, properties);
+    }
+}
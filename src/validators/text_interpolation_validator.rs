@@ -0,0 +1,82 @@
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+
+const TEXT_FIELDS: [&str; 2] = ["description", "remediation"];
+
+pub struct TextInterpolationValidator {}
+
+impl Validator for TextInterpolationValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        TEXT_FIELDS
+            .iter()
+            .filter_map(|field| {
+                let text = json_check.get(field)?.as_str()?;
+
+                match text.contains("${") {
+                    true => Some(ValidationDiagnostic::Warning {
+                        check_id: check_id.to_string(),
+                        message: format!(
+                            "{} is not interpolated, '${{...}}' is displayed as is",
+                            field
+                        ),
+                        instance_path: format!("/{}", field),
+                    }),
+                    false => None,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn validate_interpolated_remediation() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              Set the token timeout to ${values.expected_token_timeout}
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_token_timeout
+                default: 5000
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = TextInterpolationValidator {}.validate(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "remediation is not interpolated, '${...}' is displayed as is"
+                );
+                assert_eq!(instance_path, "/remediation");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+use serde_json::json;
+
+pub struct ValueRangeValidator {
+    pub min_value: f64,
+}
+
+impl Validator for ValueRangeValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        validate_value_ranges(json_check, check_id, self.min_value)
+    }
+}
+
+fn validate_value_range(
+    value: Option<&serde_json::Value>,
+    field: &str,
+    min_value: f64,
+    check_id: &str,
+    instance_path: String,
+) -> Option<ValidationDiagnostic> {
+    let value = value?;
+    let number = value.as_f64()?;
+
+    match number < min_value {
+        true => Some(ValidationDiagnostic::Warning {
+            check_id: check_id.to_string(),
+            message: format!(
+                "{} {} is lower than the minimum expected value {}",
+                field, value, min_value
+            ),
+            instance_path,
+        }),
+        false => None,
+    }
+}
+
+fn validate_value_ranges(
+    json_check: &serde_json::Value,
+    check_id: &str,
+    min_value: f64,
+) -> Vec<ValidationDiagnostic> {
+    json_check
+        .get("values")
+        .unwrap_or(&json!([]))
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .enumerate()
+        .flat_map(|(value_index, value)| {
+            let default_diagnostic = validate_value_range(
+                value.get("default"),
+                "default",
+                min_value,
+                check_id,
+                format!("/values/{:?}", value_index),
+            );
+
+            let condition_diagnostics = value
+                .get("conditions")
+                .and_then(|conditions| conditions.as_array())
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .filter_map(move |(condition_index, condition)| {
+                    validate_value_range(
+                        condition.get("value"),
+                        "value",
+                        min_value,
+                        check_id,
+                        format!("/values/{:?}/conditions/{:?}", value_index, condition_index),
+                    )
+                });
+
+            default_diagnostic.into_iter().chain(condition_diagnostics)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dsl::types::DEFAULT_MIN_VALUE;
+    use serde_json;
+
+    #[test]
+    fn validate_negative_value_default() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_token_timeout
+                default: -5000
+                conditions:
+                  - value: 30000
+                    when: env.provider == "aws"
+              - name: expected_provider
+                default: azure
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_value_ranges(&json_value, "156F64", DEFAULT_MIN_VALUE);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "default -5000 is lower than the minimum expected value 0"
+                );
+                assert_eq!(instance_path, "/values/0");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+
+    #[test]
+    fn validate_zero_value_with_positive_minimum() {
+        let input = r#"
+            values:
+              - name: expected_token_timeout
+                default: 5000
+                conditions:
+                  - value: 0
+                    when: env.provider == "aws"
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_value_ranges(&json_value, "156F64", 1.0);
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning { instance_path, .. } => {
+                assert_eq!(instance_path, "/values/0/conditions/0");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+}
@@ -1,5 +1,6 @@
+use crate::dsl::engine::find_disallowed_functions;
 use crate::dsl::types::{ValidationDiagnostic, Validator};
-use rhai::Engine;
+use rhai::{is_valid_identifier, Engine, Expr, Stmt};
 use serde_json::json;
 
 pub struct ValueValidator<'a> {
@@ -12,7 +13,12 @@ impl<'a> Validator for ValueValidator<'a> {
         json_check: &serde_json::Value,
         check_id: &str,
     ) -> Vec<ValidationDiagnostic> {
-        validate_values(json_check, check_id, self.engine)
+        let mut diagnostics = validate_values(json_check, check_id, self.engine);
+        diagnostics.extend(validate_value_names(json_check, check_id));
+        diagnostics.extend(validate_duplicate_conditions(json_check, check_id));
+        diagnostics.extend(validate_shadowed_conditions(json_check, check_id));
+        diagnostics.extend(validate_non_boolean_conditions(json_check, check_id));
+        diagnostics
     }
 }
 
@@ -29,30 +35,46 @@ fn validate_values(
         .iter()
         .enumerate()
         .flat_map(|(value_index, value)| {
-            let conditions_compilations_results: Vec<Result<_, _>> = value
+            let conditions_compilations_results: Vec<Result<(), _>> = value
                 .get("conditions")
                 .unwrap_or(&json!([]))
                 .as_array()
                 .unwrap_or(&Vec::new())
                 .iter()
                 .enumerate()
-                .map(|(condition_index, condition)| {
+                .flat_map(|(condition_index, condition)| {
                     let default_json_string = json!("");
-                    let when_expression = condition
+                    let instance_path =
+                        format!("/values/{:?}/conditions/{:?}", value_index, condition_index);
+                    let Some(when_expression) = condition
                         .get("when")
                         .unwrap_or(&default_json_string)
                         .as_str()
-                        .unwrap();
-                    engine.compile(when_expression).map_err(|error| {
-                        ValidationDiagnostic::Critical {
+                    else {
+                        return vec![Err(ValidationDiagnostic::Critical {
+                            check_id: check_id.to_string(),
+                            message: "when must be a string".to_string(),
+                            instance_path,
+                        })];
+                    };
+
+                    match engine.compile(when_expression) {
+                        Ok(ast) => find_disallowed_functions(&ast)
+                            .into_iter()
+                            .map(|function| {
+                                Err(ValidationDiagnostic::Critical {
+                                    check_id: check_id.to_string(),
+                                    message: format!("function '{}' is not allowed", function),
+                                    instance_path: instance_path.clone(),
+                                })
+                            })
+                            .collect(),
+                        Err(error) => vec![Err(ValidationDiagnostic::Critical {
                             check_id: check_id.to_string(),
                             message: error.to_string(),
-                            instance_path: format!(
-                                "/values/{:?}/conditions/{:?}",
-                                value_index, condition_index
-                            ),
-                        }
-                    })
+                            instance_path,
+                        })],
+                    }
                 })
                 .collect();
 
@@ -60,10 +82,234 @@ fn validate_values(
         })
         .partition(Result::is_ok);
 
-    return values_expression_errors
+    values_expression_errors
         .into_iter()
         .map(Result::unwrap_err)
-        .collect();
+        .collect()
+}
+
+// Expectations reference values as `values.<name>`, which requires the name to be an identifier.
+fn validate_value_names(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    json_check
+        .get("values")
+        .and_then(|values| values.as_array())
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(index, value)| {
+            let name = value.get("name")?.as_str()?;
+
+            match is_valid_identifier(name) {
+                true => None,
+                false => Some(ValidationDiagnostic::Critical {
+                    check_id: check_id.to_string(),
+                    message: format!(
+                        "value name '{}' is not a valid identifier and can't be referenced as values.{}",
+                        name, name
+                    ),
+                    instance_path: format!("/values/{:?}/name", index),
+                }),
+            }
+        })
+        .collect()
+}
+
+// Conditions are evaluated in order, so a condition repeating an earlier `when` expression of the
+// same value is never selected.
+fn validate_duplicate_conditions(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    json_check
+        .get("values")
+        .unwrap_or(&json!([]))
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .enumerate()
+        .flat_map(|(value_index, value)| {
+            let when_expressions: Vec<Option<&str>> = value
+                .get("conditions")
+                .and_then(|conditions| conditions.as_array())
+                .into_iter()
+                .flatten()
+                .map(|condition| condition.get("when").and_then(|when| when.as_str()))
+                .map(|when| when.map(str::trim))
+                .collect();
+
+            when_expressions
+                .iter()
+                .enumerate()
+                .filter_map(|(condition_index, when_expression)| {
+                    let when_expression = (*when_expression)?;
+                    let duplicated_index = when_expressions[..condition_index]
+                        .iter()
+                        .position(|previous| *previous == Some(when_expression))?;
+
+                    Some(ValidationDiagnostic::Warning {
+                        check_id: check_id.to_string(),
+                        message: format!(
+                            "condition duplicates the 'when' expression of condition {} and is never reached",
+                            duplicated_index
+                        ),
+                        instance_path: format!(
+                            "/values/{:?}/conditions/{:?}",
+                            value_index, condition_index
+                        ),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+// An unquoted `when: true` is read as a boolean rather than an expression, and is always true as well.
+fn is_always_true(when: &serde_json::Value, engine: &Engine) -> bool {
+    match when {
+        serde_json::Value::Bool(when) => *when,
+        serde_json::Value::String(when_expression) => {
+            match engine.compile_expression(when_expression) {
+                Ok(ast) => matches!(
+                    ast.statements(),
+                    [Stmt::Expr(expression)] if matches!(**expression, Expr::BoolConstant(true, _))
+                ),
+                Err(_) => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+// A condition which is always true selects its value, so the conditions following it are never
+// reached. Conditions duplicating it are already reported as duplicates.
+fn validate_shadowed_conditions(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    let engine = Engine::new_raw();
+
+    json_check
+        .get("values")
+        .unwrap_or(&json!([]))
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .enumerate()
+        .flat_map(|(value_index, value)| {
+            let whens: Vec<Option<&serde_json::Value>> = value
+                .get("conditions")
+                .and_then(|conditions| conditions.as_array())
+                .into_iter()
+                .flatten()
+                .map(|condition| condition.get("when"))
+                .collect();
+            let when_expressions: Vec<Option<&str>> = whens
+                .iter()
+                .map(|when| when.and_then(|when| when.as_str()))
+                .map(|when| when.map(str::trim))
+                .collect();
+
+            let always_true_index = whens
+                .iter()
+                .position(|when| when.is_some_and(|when| is_always_true(when, &engine)));
+            let Some(always_true_index) = always_true_index else {
+                return vec![];
+            };
+
+            when_expressions
+                .iter()
+                .enumerate()
+                .skip(always_true_index + 1)
+                .filter(|(_, when_expression)| {
+                    **when_expression != when_expressions[always_true_index]
+                })
+                .map(|(condition_index, _)| ValidationDiagnostic::Warning {
+                    check_id: check_id.to_string(),
+                    message: format!(
+                        "condition is never reached as condition {} is always true. Move the always true condition last",
+                        always_true_index
+                    ),
+                    instance_path: format!(
+                        "/values/{:?}/conditions/{:?}",
+                        value_index, condition_index
+                    ),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+// Whether the expression is a plain access or constant, without any comparison, logical operator
+// or call which could make it a boolean.
+fn is_plain_access(expression: &Expr) -> bool {
+    match expression {
+        Expr::Variable(..)
+        | Expr::Property(..)
+        | Expr::StringConstant(..)
+        | Expr::IntegerConstant(..)
+        | Expr::FloatConstant(..) => true,
+        Expr::Dot(binary, _, _) | Expr::Index(binary, _, _) => is_plain_access(&binary.rhs),
+        _ => false,
+    }
+}
+
+fn is_likely_not_boolean(when_expression: &str, engine: &Engine) -> bool {
+    match engine.compile_expression(when_expression) {
+        Ok(ast) => matches!(
+            ast.statements(),
+            [Stmt::Expr(expression)] if is_plain_access(expression)
+        ),
+        Err(_) => false,
+    }
+}
+
+// A `when` expression which is not a boolean, e.g. `env.provider`, is evaluated by its
+// truthiness. Genuine boolean variables are fine, hence only a warning.
+fn validate_non_boolean_conditions(
+    json_check: &serde_json::Value,
+    check_id: &str,
+) -> Vec<ValidationDiagnostic> {
+    let engine = Engine::new_raw();
+
+    json_check
+        .get("values")
+        .unwrap_or(&json!([]))
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .enumerate()
+        .flat_map(|(value_index, value)| {
+            value
+                .get("conditions")
+                .and_then(|conditions| conditions.as_array())
+                .into_iter()
+                .flatten()
+                .enumerate()
+                .filter_map(|(condition_index, condition)| {
+                    let when_expression = condition.get("when")?.as_str()?;
+
+                    match is_likely_not_boolean(when_expression, &engine) {
+                        true => Some(ValidationDiagnostic::Warning {
+                            check_id: check_id.to_string(),
+                            message: format!(
+                                "'when' expression '{}' has no comparison and may not be a boolean, e.g. use {} == \"value\"",
+                                when_expression.trim(),
+                                when_expression.trim()
+                            ),
+                            instance_path: format!(
+                                "/values/{:?}/conditions/{:?}",
+                                value_index, condition_index
+                            ),
+                        }),
+                        false => None,
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -171,4 +417,256 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn validate_duplicate_conditions_check() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            values:
+              - name: expected_token_timeout
+                default: 5000
+                conditions:
+                  - value: 30000
+                    when: env.provider == "aws"
+                  - value: 20000
+                    when: env.provider == "aws"
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_duplicate_conditions(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "condition duplicates the 'when' expression of condition 0 and is never reached"
+                );
+                assert_eq!(instance_path, "/values/0/conditions/1");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+
+    #[test]
+    fn validate_shadowed_conditions_check() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            values:
+              - name: expected_token_timeout
+                default: 5000
+                conditions:
+                  - value: 30000
+                    when: env.provider == "azure"
+                  - value: 20000
+                    when: "true"
+                  - value: 10000
+                    when: env.provider == "aws"
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_shadowed_conditions(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "condition is never reached as condition 1 is always true. Move the always true condition last"
+                );
+                assert_eq!(instance_path, "/values/0/conditions/2");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+
+    #[test]
+    fn validate_unquoted_true_condition() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Abstract
+              The value of the Corosync `token` timeout is not set as recommended.
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+                argument: totem.token
+            values:
+              - name: expected_token_timeout
+                default: 5000
+                conditions:
+                  - value: 20000
+                    when: true
+                  - value: 30000
+                    when: env.provider == "azure"
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+        "#;
+
+        let engine = Engine::new();
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = ValueValidator { engine: &engine }.validate(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 2);
+        match &validation_errors[0] {
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+            ValidationDiagnostic::Critical {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(message, "when must be a string");
+                assert_eq!(instance_path, "/values/0/conditions/0");
+            }
+        }
+        match &validation_errors[1] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "condition is never reached as condition 0 is always true. Move the always true condition last"
+                );
+                assert_eq!(instance_path, "/values/0/conditions/1");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
+
+    #[test]
+    fn validate_value_names_are_identifiers() {
+        let input = r#"
+            values:
+              - name: expected-token-timeout
+                default: 5000
+              - name: expected_token_timeout
+                default: 5000
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_value_names(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Critical {
+                message,
+                instance_path,
+                ..
+            } => {
+                assert_eq!(
+                    message,
+                    "value name 'expected-token-timeout' is not a valid identifier and can't be referenced as values.expected-token-timeout"
+                );
+                assert_eq!(instance_path, "/values/0/name");
+            }
+            w @ ValidationDiagnostic::Warning { .. } => panic!("Unexpected variant {:?}", w),
+        }
+    }
+
+    #[test]
+    fn validate_non_boolean_conditions_check() {
+        let input = r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              ## Remediation
+              ...
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            values:
+              - name: expected_token_timeout
+                default: 5000
+                conditions:
+                  - value: 30000
+                    when: env.provider == "aws"
+                  - value: 20000
+                    when: env.provider
+            expectations:
+              - name: timeout
+                expect: facts.corosync_token_timeout == values.expected_token_timeout
+        "#;
+
+        let json_value: serde_json::Value =
+            serde_yaml::from_str(input).expect("Unable to parse yaml");
+        let validation_errors = validate_non_boolean_conditions(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "'when' expression 'env.provider' has no comparison and may not be a boolean, e.g. use env.provider == \"value\""
+                );
+                assert_eq!(instance_path, "/values/0/conditions/1");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+    }
 }
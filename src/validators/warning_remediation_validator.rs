@@ -0,0 +1,138 @@
+use crate::dsl::types::{ValidationDiagnostic, Validator};
+use crate::validators::expectation_validator::collect_enum_return_values;
+use rhai::Engine;
+use serde_json::json;
+
+// The expectation rule already requires a warning_message when expect_enum can return "warning",
+// this rule asks the remediation to cover the warning state as well.
+pub struct WarningRemediationValidator {}
+
+impl Validator for WarningRemediationValidator {
+    fn validate(
+        &self,
+        json_check: &serde_json::Value,
+        check_id: &str,
+    ) -> Vec<ValidationDiagnostic> {
+        let mentions_warning = json_check
+            .get("remediation")
+            .and_then(|remediation| remediation.as_str())
+            .is_some_and(|remediation| remediation.to_lowercase().contains("warning"));
+        if mentions_warning {
+            return vec![];
+        }
+
+        let engine = Engine::new_raw();
+        json_check
+            .get("expectations")
+            .unwrap_or(&json!([]))
+            .as_array()
+            .unwrap_or(&Vec::new())
+            .iter()
+            .enumerate()
+            .filter(|(_, expectation)| {
+                expectation
+                    .get("expect_enum")
+                    .and_then(|expression| expression.as_str())
+                    .and_then(|expression| engine.compile(expression).ok())
+                    .is_some_and(|ast| {
+                        collect_enum_return_values(&ast).contains(&"warning".to_string())
+                    })
+            })
+            .map(|(index, _)| ValidationDiagnostic::Warning {
+                check_id: check_id.to_string(),
+                message: "expect_enum can return \"warning\" but the remediation does not mention the warning state".to_string(),
+                instance_path: format!("/expectations/{:?}", index),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    fn check(remediation: &str, warning_return: &str) -> serde_json::Value {
+        let input = format!(
+            r#"
+            id: 156F64
+            name: Corosync configuration file
+            group: Corosync
+            description: |
+              Corosync `token` timeout is set to expected value
+            remediation: |
+              {}
+            facts:
+              - name: corosync_token_timeout
+                gatherer: corosync.conf
+            expectations:
+              - name: timeout
+                expect_enum: |
+                  if facts.corosync_token_timeout == 5000 {{
+                    "passing"
+                  }} else if facts.corosync_token_timeout == 3000 {{
+                    {}
+                  }} else {{
+                    "critical"
+                  }}
+                failure_message: Timeout is not set as expected
+                warning_message: Timeout is lower than recommended
+            "#,
+            remediation, warning_return
+        );
+
+        serde_yaml::from_str(&input).expect("Unable to parse yaml")
+    }
+
+    #[test]
+    fn validate_remediation_without_warning_state() {
+        let json_value = check("Set the token timeout to 5000", "\"warning\"");
+        let validation_errors = WarningRemediationValidator {}.validate(&json_value, "156F64");
+
+        assert!(validation_errors.len() == 1);
+        match &validation_errors[0] {
+            ValidationDiagnostic::Warning {
+                check_id,
+                message,
+                instance_path,
+            } => {
+                assert_eq!(check_id, "156F64");
+                assert_eq!(
+                    message,
+                    "expect_enum can return \"warning\" but the remediation does not mention the warning state"
+                );
+                assert_eq!(instance_path, "/expectations/0");
+            }
+            e @ ValidationDiagnostic::Critical { .. } => panic!("Unexpected variant {:?}", e),
+        }
+
+        let json_value = check(
+            "Set the token timeout to 5000, a Warning is raised for 3000",
+            "\"warning\"",
+        );
+        assert!(WarningRemediationValidator {}
+            .validate(&json_value, "156F64")
+            .is_empty());
+    }
+
+    #[test]
+    fn validate_expect_enum_without_warning_return() {
+        let json_value = check("Set the token timeout to 5000", "\"critical\"");
+
+        assert!(WarningRemediationValidator {}
+            .validate(&json_value, "156F64")
+            .is_empty());
+    }
+
+    #[test]
+    fn validate_expect_enum_comparing_to_warning() {
+        let json_value = check(
+            "Set the token timeout to 5000",
+            "if facts.corosync_token_timeout == \"warning\" { \"critical\" } else { \"passing\" }",
+        );
+
+        assert!(WarningRemediationValidator {}
+            .validate(&json_value, "156F64")
+            .is_empty());
+    }
+}
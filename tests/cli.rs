@@ -33,10 +33,18 @@ fn validates_deprecated_check() -> Result<(), Box<dyn std::error::Error>> {
     cmd.arg("lint")
         .arg("-f")
         .arg("tests/fixtures/deprecated_check.yml");
-    cmd.assert().failure().stdout(predicate::str::contains(
+    cmd.assert().success().stdout(predicate::str::contains(
         " Property \'premium\' is deprecated and will be removed in the future\n",
     ));
 
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--strict")
+        .arg("-f")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert().code(1);
+
     Ok(())
 }
 
@@ -44,10 +52,1313 @@ fn validates_deprecated_check() -> Result<(), Box<dyn std::error::Error>> {
 fn file_doesnt_exist() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("tlint")?;
 
-    cmd.arg("lint").arg("-f").arg("test/file/doesnt/exist");
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("-f")
+        .arg("test/file/doesnt/exist");
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("Missing file"))
+        .stdout(predicate::str::contains("No such file or directory"))
+        .stdout(predicate::str::contains("Parse error").not());
+
+    Ok(())
+}
+
+#[test]
+fn prints_file_status_in_verbose_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--verbose")
+        .arg("-f")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "✗ tests/fixtures/deprecated_check.yml (1 warning)\n",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn suppresses_file_status_in_quiet_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--verbose")
+        .arg("--quiet")
+        .arg("-f")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("tests/fixtures/deprecated_check.yml").not())
+        .stdout(predicate::str::contains(
+            "Property 'premium' is deprecated and will be removed in the future",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn validates_files_from_stdin() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--files-from")
+        .arg("-")
+        .write_stdin("tests/fixtures/check.yml\ntests/fixtures/invalid_check.yml\n");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "  Parse error   - missing field `id` at line 2 column 1\n",
+    ));
+
+    let mut cmd = assert_cmd::Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--verbose")
+        .arg("--files-from")
+        .arg("-")
+        .write_stdin("tests/fixtures/check.yml\n\ntests/fixtures/check.yml\n");
+    cmd.assert()
+        .success()
+        .stdout("✓ tests/fixtures/check.yml\n✓ tests/fixtures/check.yml\n");
+
+    Ok(())
+}
+
+#[test]
+fn validates_opt_in_rules() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("-f")
+        .arg("tests/fixtures/placeholder_check.yml");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--rule")
+        .arg("default")
+        .arg("--rule")
+        .arg("placeholder")
+        .arg("-f")
+        .arg("tests/fixtures/placeholder_check.yml");
+    cmd.assert().success().stdout(predicate::str::contains(
+        " template placeholder `{{ platform.corosync.expectedTokenTimeout }}` appears unrendered\n",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn outputs_codeclimate_report() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("codeclimate")
+        .arg("-f")
+        .arg("tests/fixtures/deprecated_check.yml");
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(report[0]["severity"], "minor");
+    assert_eq!(
+        report[0]["location"]["path"],
+        "tests/fixtures/deprecated_check.yml"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn outputs_sarif_report() -> Result<(), Box<dyn std::error::Error>> {
+    let output = std::env::temp_dir().join(format!("tlint-report-{}.sarif", std::process::id()));
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("sarif")
+        .arg("--docs-base-url")
+        .arg("https://docs.example.com/tlint")
+        .arg("--output")
+        .arg(&output)
+        .arg("-f")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert().success();
+
+    let report: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output)?)?;
+    std::fs::remove_file(&output)?;
+
+    let rules = &report["runs"][0]["tool"]["driver"]["rules"];
+    assert_eq!(rules[0]["id"], "schema");
+    assert_eq!(rules[0]["helpUri"], "https://docs.example.com/tlint#schema");
+    assert!(rules[0]["fullDescription"]["text"].is_string());
+    assert_eq!(report["runs"][0]["results"][0]["ruleId"], "schema");
+    assert_eq!(
+        report["properties"]["tlint_version"],
+        env!("CARGO_PKG_VERSION")
+    );
+    assert_eq!(report["properties"]["schema_version"], "main");
+
+    Ok(())
+}
+
+#[test]
+fn outputs_json_report() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("json")
+        .arg("-f")
+        .arg("tests/fixtures/check.yml");
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(report["ok"], true);
+    assert_eq!(report["files_checked"], 1);
+    assert_eq!(report["diagnostics"], serde_json::json!([]));
+    assert_eq!(report["tlint_version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(report["schema_version"], "main");
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("json")
+        .arg("tests/fixtures/deprecated_check.yml")
+        .arg("tests/fixtures/invalid_check.yml");
+    let output = cmd.assert().failure().get_output().stdout.clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(report["diagnostics"][0]["check_id"], "156F64");
+    assert_eq!(report["diagnostics"][0]["group"], "Corosync");
+    assert_eq!(report["diagnostics"][1]["group"], serde_json::Value::Null);
+
+    Ok(())
+}
+
+#[test]
+fn shows_linted_check() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--show")
+        .arg("-f")
+        .arg("tests/fixtures/check.yml");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Corosync configuration file"));
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--show")
+        .arg("-f")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Corosync configuration file").not());
+
+    Ok(())
+}
+
+#[test]
+fn expands_environment_variables_in_path() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.env("TLINT_FIXTURES_DIR", "tests/fixtures")
+        .arg("lint")
+        .arg("--expand-env")
+        .arg("-f")
+        .arg("${TLINT_FIXTURES_DIR}/check.yml");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.env_remove("TLINT_UNSET_DIR")
+        .arg("lint")
+        .arg("--expand-env")
+        .arg("-f")
+        .arg("$TLINT_UNSET_DIR/check.yml");
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "environment variable 'TLINT_UNSET_DIR' is not set",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn shows_check_values() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("show").arg("-f").arg("tests/fixtures/check.yml");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Values"))
+        .stdout(predicate::str::contains("expected_token_timeout"));
+
+    Ok(())
+}
+
+#[test]
+fn validates_check_with_byte_order_mark() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("-f")
+        .arg("tests/fixtures/bom_check.yml");
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn reports_non_utf8_input() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .write_stdin(vec![0x69, 0x64, 0x3a, 0x20, 0xff, 0xfe]);
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("input is not valid UTF-8"));
+
+    Ok(())
+}
+
+#[test]
+fn prints_counts_only() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--count-only")
+        .arg("-f")
+        .arg("tests/fixtures/invalid_check.yml");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("files checked: 1, errors: "))
+        .stdout(predicate::str::contains("path:").not());
+
+    Ok(())
+}
+
+#[test]
+fn validates_catalog() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("catalog")
+        .arg("tests/fixtures/catalog")
+        .arg("--approved-groups")
+        .arg("Corosync,Pacemaker");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("check id '156F64' is not unique"))
+        .stdout(predicate::str::contains("group 'Pacemaker' has no checks"));
+
+    Ok(())
+}
+
+#[test]
+fn catalog_directory_doesnt_exist() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("catalog").arg("test/directory/doesnt/exist");
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("Invalid input"))
+        .stdout(predicate::str::contains(
+            "unable to scan test/directory/doesnt/exist",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn inventory_directory_doesnt_exist() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("inventory").arg("test/directory/doesnt/exist");
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Invalid input"))
+        .stderr(predicate::str::contains(
+            "unable to scan test/directory/doesnt/exist",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn suggests_expect_for_interpolated_expect_same_messages() -> Result<(), Box<dyn std::error::Error>>
+{
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("-f")
+        .arg("tests/fixtures/expect_same_interpolation_check.yml");
     cmd.assert()
         .failure()
-        .stderr(predicate::str::contains("No such file or directory"));
+        .stdout(predicate::str::contains(
+            "String interpolation is not allowed here",
+        ))
+        .stdout(predicate::str::contains(
+            "this check uses expect_same, so failure_message interpolation is unavailable",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn writes_report_to_output_file() -> Result<(), Box<dyn std::error::Error>> {
+    let output = std::env::temp_dir().join(format!("tlint-report-{}.json", std::process::id()));
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("json")
+        .arg("--output")
+        .arg(&output)
+        .arg("-f")
+        .arg("tests/fixtures/check.yml");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("report written to"));
+
+    let report: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output)?)?;
+    std::fs::remove_file(&output)?;
+
+    assert_eq!(report["ok"], true);
+    assert_eq!(report["files_checked"], 1);
+
+    Ok(())
+}
+
+#[test]
+fn reports_deprecations_only() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--deprecations-only")
+        .arg("tests/fixtures/deprecated_check.yml")
+        .arg("tests/fixtures/check.yml");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "  file: tests/fixtures/deprecated_check.yml\n",
+        ))
+        .stdout(predicate::str::contains(
+            "Property 'premium' is deprecated and will be removed in the future",
+        ))
+        .stdout(predicate::str::contains("tests/fixtures/check.yml").not())
+        .stdout(predicate::str::contains("rule:").not());
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--deprecations-only")
+        .arg("--strict")
+        .arg("-f")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert().failure();
+
+    Ok(())
+}
+
+#[test]
+fn formats_diagnostics_with_template() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--error-format")
+        .arg("{file}:{line}: {severity}: {message} ({path})")
+        .arg("-f")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "tests/fixtures/deprecated_check.yml:1: warning: Property 'premium' is deprecated and will be removed in the future (/premium)",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn ignores_matching_files_in_directory() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint").arg("-f").arg("tests/fixtures/ignore");
+    cmd.assert().failure();
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--ignore")
+        .arg("*_template.yaml")
+        .arg("-f")
+        .arg("tests/fixtures/ignore");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--ignore")
+        .arg("tests/fixtures/ignore/check_*.yaml")
+        .arg("-f")
+        .arg("tests/fixtures/ignore");
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn uses_warn_exit_code_for_warnings() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--warn-exit-code")
+        .arg("3")
+        .arg("-f")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert().code(3);
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--warn-exit-code")
+        .arg("3")
+        .arg("-f")
+        .arg("tests/fixtures/invalid_check.yml");
+    cmd.assert().code(1);
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--warn-exit-code")
+        .arg("3")
+        .arg("tests/fixtures/deprecated_check.yml")
+        .arg("tests/fixtures/schema_error_check.yml");
+    cmd.assert().code(3);
+
+    Ok(())
+}
+
+#[test]
+fn evaluates_expectations_with_sample_facts() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("eval")
+        .arg("tests/fixtures/check.yml")
+        .arg("--facts")
+        .arg("tests/fixtures/check_facts.json");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("timeout: true"));
+
+    Ok(())
+}
+
+#[test]
+fn outputs_junit_report() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("junit")
+        .arg("--junit-granularity")
+        .arg("expectation")
+        .arg("-f")
+        .arg("tests/fixtures/check.yml");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "<testcase name=\"tests/fixtures/check.yml timeout\" classname=\"tests/fixtures/check.yml\"/>",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn prints_reproducible_output() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.env("CLICOLOR_FORCE", "1")
+        .arg("lint")
+        .arg("--reproducible")
+        .arg("--verbose")
+        .arg("-f")
+        .arg("tests/fixtures/reproducible");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("\u{1b}[").not())
+        .stdout(predicate::str::is_match(
+            "(?s)a_invalid_check.yml.*b_deprecated_check.yml",
+        )?);
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--reproducible")
+        .arg("--color")
+        .arg("always")
+        .arg("-f")
+        .arg("tests/fixtures/reproducible");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("\u{1b}["));
+
+    Ok(())
+}
+
+#[test]
+fn validates_allowed_groups() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--groups")
+        .arg("tests/fixtures/groups.txt")
+        .arg("-f")
+        .arg("tests/fixtures/check.yml");
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn refines_rules_with_check_metadata() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("-f")
+        .arg("tests/fixtures/check_rules_check.yml");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--rule")
+        .arg("schema")
+        .arg("-f")
+        .arg("tests/fixtures/check_rules_check.yml");
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn validates_inline_check() -> Result<(), Box<dyn std::error::Error>> {
+    let check = std::fs::read_to_string("tests/fixtures/check.yml")?;
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint").arg("--inline").arg(check);
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--inline")
+        .arg("id: 156F64")
+        .arg("-f")
+        .arg("tests/fixtures/check.yml");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    Ok(())
+}
+
+#[test]
+fn validates_multiple_targets() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--verbose")
+        .arg("tests/fixtures/check.yml")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("✓ tests/fixtures/check.yml\n"))
+        .stdout(predicate::str::contains(
+            "✗ tests/fixtures/deprecated_check.yml (1 warning)\n",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn validates_only_the_schema_in_fast_mode() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--fast")
+        .arg("tests/fixtures/expect_same_interpolation_check.yml");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--fast")
+        .arg("--env-keys")
+        .arg("tests/fixtures/groups.txt")
+        .arg("tests/fixtures/check.yml");
+    cmd.assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    Ok(())
+}
+
+#[test]
+fn normalizes_check_id() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("normalize-id")
+        .arg("tests/fixtures/lowercase_id_check.yml");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("id: 156F64\n"));
+
+    Ok(())
+}
+
+#[test]
+fn reports_unreachable_schema_url() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--schema-url")
+        .arg("http://127.0.0.1:9/check_definition.schema.json")
+        .arg("tests/fixtures/check.yml");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("unable to fetch the schema"));
+
+    Ok(())
+}
+
+#[test]
+fn wraps_long_messages() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("--wrap")
+        .arg("50")
+        .arg("tests/fixtures/expect_same_interpolation_check.yml");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "  156F64   - String interpolation is not allowed\n             here. Use a plain failure_message\n",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn reports_exec_command_diagnostics() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("--exec")
+        .arg("sh tests/fixtures/exec/registry.sh")
+        .arg("tests/fixtures/check.yml");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "  156F64   - check check.yml is not registered\n  path: /id\n",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn reports_failing_exec_command() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("--exec")
+        .arg("false")
+        .arg("tests/fixtures/check.yml");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "'false' failed with exit status: 1",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn writes_junit_report_to_output_file() -> Result<(), Box<dyn std::error::Error>> {
+    use xml::reader::{EventReader, XmlEvent};
+
+    let output = std::env::temp_dir().join(format!("tlint-report-{}.xml", std::process::id()));
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("tests/fixtures")
+        .arg("--format")
+        .arg("junit")
+        .arg("--output")
+        .arg(&output);
+    cmd.assert().failure().stdout(predicate::str::is_empty());
+
+    let report = std::fs::read_to_string(&output)?;
+    std::fs::remove_file(&output)?;
+
+    let mut elements = vec![];
+    let mut testcase = String::new();
+    for event in EventReader::new(report.as_bytes()) {
+        if let XmlEvent::StartElement {
+            name, attributes, ..
+        } = event?
+        {
+            let attribute = |key: &str| {
+                attributes
+                    .iter()
+                    .find(|attribute| attribute.name.local_name == key)
+                    .map(|attribute| attribute.value.clone())
+                    .unwrap_or_default()
+            };
+
+            match name.local_name.as_str() {
+                "testcase" => testcase = attribute("name"),
+                "failure" => elements.push(format!("{} {}", testcase, attribute("type"))),
+                element => elements.push(element.to_string()),
+            }
+        }
+    }
+
+    assert_eq!(elements[..2], ["testsuites", "testsuite"]);
+    assert!(elements.contains(&"tests/fixtures/invalid_check.yml parse_error".to_string()));
+    assert!(elements.contains(&"tests/fixtures/deprecated_check.yml warning".to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn uses_highest_exit_code_of_the_diagnostics() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--warning-exit")
+        .arg("2")
+        .arg("--error-exit")
+        .arg("3")
+        .arg("tests/fixtures/deprecated_check.yml")
+        .arg("tests/fixtures/expect_same_interpolation_check.yml");
+    cmd.assert().code(3);
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--parse-error-exit")
+        .arg("4")
+        .arg("--warning-exit")
+        .arg("0")
+        .arg("tests/fixtures/deprecated_check.yml")
+        .arg("tests/fixtures/invalid_check.yml");
+    cmd.assert().code(4);
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--warning-exit")
+        .arg("0")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert().success();
+
+    Ok(())
+}
+
+#[test]
+fn tags_diagnostics_with_their_rule() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("json")
+        .arg("tests/fixtures/schema_error_check.yml");
+    let output = cmd.assert().failure().get_output().stdout.clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(report["diagnostics"][0]["rule"], "schema");
+    assert_eq!(report["diagnostics"][0]["instance_path"], "/expectations/0");
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--verbose")
+        .arg("tests/fixtures/schema_error_check.yml");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "  path: /expectations/0\n  rule: schema\n",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn reuses_cached_diagnostics_of_unchanged_files() -> Result<(), Box<dyn std::error::Error>> {
+    let cache = std::env::temp_dir().join(format!("tlint-cache-{}", std::process::id()));
+
+    let mut outputs = vec![];
+    for _ in 0..2 {
+        let mut cmd = Command::cargo_bin("tlint")?;
+
+        cmd.arg("lint")
+            .arg("--cache")
+            .arg(&cache)
+            .arg("--format")
+            .arg("json")
+            .arg("tests/fixtures/schema_error_check.yml");
+        outputs.push(cmd.assert().failure().get_output().stdout.clone());
+    }
+
+    assert_eq!(outputs[0], outputs[1]);
+    assert_eq!(std::fs::read_dir(&cache)?.count(), 1);
+
+    std::fs::remove_dir_all(&cache)?;
+
+    Ok(())
+}
+
+#[test]
+fn colors_output_as_requested() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--color")
+        .arg("never")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert()
+        .stdout(predicate::str::contains("premium"))
+        .stdout(predicate::str::contains("\u{1b}[").not());
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("--color")
+        .arg("never")
+        .arg("show")
+        .arg("-f")
+        .arg("tests/fixtures/check.yml");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\u{1b}[").not());
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--color")
+        .arg("always")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert().stdout(predicate::str::contains("\u{1b}["));
+
+    Ok(())
+}
+
+#[test]
+fn skips_hidden_files_in_directory() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint").arg("tests/fixtures/hidden");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--include-hidden")
+        .arg("tests/fixtures/hidden");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("missing field `id`"));
+
+    Ok(())
+}
+
+#[test]
+fn writes_summary_json_next_to_human_output() -> Result<(), Box<dyn std::error::Error>> {
+    let summary = std::env::temp_dir().join(format!("tlint-summary-{}.json", std::process::id()));
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--summary-json")
+        .arg(&summary)
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert().stdout(predicate::str::contains(
+        "Property 'premium' is deprecated and will be removed in the future",
+    ));
+
+    let report: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&summary)?)?;
+    assert_eq!(
+        report,
+        serde_json::json!({
+            "ok": false,
+            "files_checked": 1,
+            "errors": 0,
+            "warnings": 1,
+            "parse_errors": 0,
+            "missing_files": 0,
+            "rules": ["schema", "expectation", "value", "metadata", "fact", "id-case"]
+        })
+    );
+
+    std::fs::remove_file(&summary)?;
+
+    Ok(())
+}
+
+#[test]
+fn labels_stdin_with_virtual_filename() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = assert_cmd::Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("json")
+        .arg("--stdin-filename")
+        .arg("checks/156F64.yaml")
+        .write_stdin(std::fs::read_to_string(
+            "tests/fixtures/deprecated_check.yml",
+        )?);
+    let output = cmd.assert().get_output().stdout.clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(report["diagnostics"][0]["file"], "checks/156F64.yaml");
+
+    let mut cmd = assert_cmd::Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--verbose")
+        .arg("--stdin-filename")
+        .arg("checks/156F64.yaml")
+        .write_stdin(std::fs::read_to_string("tests/fixtures/check.yml")?);
+    cmd.assert().success().stdout("✓ checks/156F64.yaml\n");
+
+    Ok(())
+}
+
+#[test]
+fn prints_effective_config() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--print-config")
+        .arg("--rule")
+        .arg("placeholder")
+        .arg("--strict-schema")
+        .arg("--format")
+        .arg("json")
+        .arg("tests/fixtures/invalid_check.yml");
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let config: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(
+        config["rules"],
+        serde_json::json!(["placeholder", "strict-schema"])
+    );
+    assert_eq!(config["format"], "json");
+    assert_eq!(config["exit_codes"]["warning"], 0);
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--print-config")
+        .arg("--deprecations-only");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("rules: deprecation\n"))
+        .stdout(predicate::str::contains(
+            "exit codes: warning 0, error 1, parse error 1\n",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn reports_paths_relative_to_base_directory() -> Result<(), Box<dyn std::error::Error>> {
+    let file = std::env::current_dir()?.join("tests/fixtures/deprecated_check.yml");
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint").arg("--format").arg("json").arg(&file);
+    let output = cmd.assert().get_output().stdout.clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(
+        report["diagnostics"][0]["file"],
+        "tests/fixtures/deprecated_check.yml"
+    );
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--relative-to")
+        .arg("tests/fixtures")
+        .arg("--format")
+        .arg("json")
+        .arg(&file);
+    let output = cmd.assert().get_output().stdout.clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(report["diagnostics"][0]["file"], "deprecated_check.yml");
+
+    Ok(())
+}
+
+#[test]
+fn outputs_gatherer_inventory() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("inventory")
+        .arg("tests/fixtures/inventory")
+        .arg("--format")
+        .arg("json");
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let inventory: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(
+        inventory["gatherers"],
+        serde_json::json!(["corosync.conf", "sbd_config"])
+    );
+    assert_eq!(inventory["checks"][0]["id"], "0B6DB2");
+    assert_eq!(
+        inventory["checks"][0]["facts"][0],
+        serde_json::json!({
+            "name": "sbd_watchdog_timeout",
+            "gatherer": "sbd_config",
+            "argument": "SBD_WATCHDOG_TIMEOUT"
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn silences_schema_deprecations_only() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-deprecation-warnings")
+        .arg("tests/fixtures/deprecated_check.yml");
+    cmd.assert().success().stdout("");
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-deprecation-warnings")
+        .arg("--format")
+        .arg("json")
+        .arg("tests/fixtures/schema_error_check.yml");
+    let output = cmd.assert().failure().get_output().stdout.clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(report["diagnostics"][0]["rule"], "schema");
+    assert_eq!(report["diagnostics"][0]["severity"], "critical");
+
+    Ok(())
+}
+
+#[test]
+fn groups_human_output() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("--group-by")
+        .arg("severity")
+        .arg("-f")
+        .arg("tests/fixtures/reproducible");
+    cmd.assert()
+        .stdout(predicate::str::contains("parse error (1)"))
+        .stdout(predicate::str::contains("warning (1)"))
+        .stdout(predicate::str::contains(
+            "  file: tests/fixtures/reproducible/b_deprecated_check.yml",
+        ));
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--group-by")
+        .arg("file")
+        .arg("--format")
+        .arg("json")
+        .arg("-f")
+        .arg("tests/fixtures/reproducible");
+    cmd.assert().stdout(predicate::str::contains("(1)").not());
+
+    Ok(())
+}
+
+#[test]
+fn lints_files_listed_by_manifest() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("--verbose")
+        .arg("--manifest")
+        .arg("tests/fixtures/manifest/manifest.json");
+    cmd.assert()
+        .stdout(predicate::str::contains(
+            "✓ tests/fixtures/manifest/check.yml\n",
+        ))
+        .stdout(predicate::str::contains(
+            "tests/fixtures/manifest/../deprecated_check.yml",
+        ))
+        .stdout(predicate::str::contains("premium"));
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("--manifest")
+        .arg("tests/fixtures/manifest/missing_manifest.json");
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "file listed in the manifest is not found",
+    ));
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("--allow-parse-errors")
+        .arg("--manifest")
+        .arg("tests/fixtures/manifest/missing_manifest.json");
+    cmd.assert().code(1).stdout(predicate::str::contains(
+        "Missing file   - file listed in the manifest is not found",
+    ));
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("--group-by")
+        .arg("severity")
+        .arg("--manifest")
+        .arg("tests/fixtures/manifest/missing_manifest.json");
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("missing file (1)"))
+        .stdout(predicate::str::contains("Parse error").not());
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--format")
+        .arg("json")
+        .arg("--manifest")
+        .arg("tests/fixtures/manifest/missing_manifest.json");
+    let output = cmd.assert().failure().get_output().stdout.clone();
+
+    let report: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(report["diagnostics"][0]["severity"], "missing_file");
+    assert_eq!(
+        report["diagnostics"][0]["message"],
+        "file listed in the manifest is not found"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn allows_parse_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("--verbose")
+        .arg("--allow-parse-errors")
+        .arg("-f")
+        .arg("tests/fixtures/partial");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "✓ tests/fixtures/partial/check.yml",
+        ))
+        .stdout(predicate::str::contains("missing field `id`"));
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--allow-parse-errors")
+        .arg("--count-only")
+        .arg("--format")
+        .arg("json")
+        .arg("-f")
+        .arg("tests/fixtures/partial");
+    let output = cmd.assert().success().get_output().stdout.clone();
+
+    let counts: serde_json::Value = serde_json::from_slice(&output)?;
+    assert_eq!(counts["warnings"], 1);
+    assert_eq!(counts["parse_errors"], 0);
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--allow-parse-errors")
+        .arg("--strict")
+        .arg("-f")
+        .arg("tests/fixtures/partial");
+    cmd.assert().code(1);
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--parse-error-exit")
+        .arg("3")
+        .arg("-f")
+        .arg("tests/fixtures/partial");
+    cmd.assert().code(3);
+
+    Ok(())
+}
+
+#[test]
+fn validates_https_links() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("-f")
+        .arg("tests/fixtures/mixed_scheme_links_check.yml");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("--rule")
+        .arg("https-link")
+        .arg("-f")
+        .arg("tests/fixtures/mixed_scheme_links_check.yml");
+    cmd.assert().success().stdout(predicate::str::contains(
+        "link 'http://documentation.suse.com/sle-ha/' is also given as 'https://documentation.suse.com/sle-ha/' in the check",
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn prints_shell_completions() -> Result<(), Box<dyn std::error::Error>> {
+    for shell in ["bash", "zsh", "fish", "powershell"] {
+        let mut cmd = Command::cargo_bin("tlint")?;
+
+        cmd.arg("completions").arg(shell);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::is_empty().not())
+            .stdout(predicate::str::contains("tlint"));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn reports_unreadable_targets_with_the_others() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("tlint")?;
+
+    cmd.arg("lint")
+        .arg("--no-color")
+        .arg("--verbose")
+        .arg("tests/fixtures/check.yml")
+        .arg("tests/fixtures/does_not_exist.yml");
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("✓ tests/fixtures/check.yml"))
+        .stdout(predicate::str::contains(
+            "unable to read tests/fixtures/does_not_exist.yml",
+        ));
 
     Ok(())
 }
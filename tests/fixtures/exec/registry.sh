@@ -0,0 +1,2 @@
+#!/bin/sh
+echo "warning /id check $(basename "$1") is not registered"
@@ -1,7 +1,7 @@
 use wasm_bindgen::prelude::*;
 use serde::{Serialize, Deserialize};
-use rhai::Engine;
 use tlint::validate;
+use tlint::dsl::engine::get_engine;
 use tlint::dsl::types::{Check, ValidationDiagnostic};
 
 #[derive(Serialize, Deserialize)]
@@ -12,7 +12,7 @@ struct ValidationResult {
 
 #[wasm_bindgen]
 pub fn lint(content: String) -> JsValue {
-    let engine = Engine::new_raw();
+    let engine = get_engine();
 
     let json_value: serde_json::Value = serde_yaml::from_str(&content)
         .expect("Unable to parse the YAML into a JSON payload");